@@ -1,4 +1,4 @@
-use crate::{Literal, expr::ExprKind};
+use crate::{Literal, expr::ExprKind, stdlib::bytes};
 
 pub struct AstPrinter;
 
@@ -29,9 +29,12 @@ impl AstPrinter {
     }
     pub fn print_literal(literal: &Literal) -> String {
         match literal {
+            Literal::Int(n) => n.to_string(),
+            Literal::BigInt(n) => n.to_string(),
             Literal::Number(n) => n.to_string(),
             Literal::Boolean(n) => n.to_string(),
             Literal::String(n) => n.to_string(),
+            Literal::Bytes(n) => bytes::display(n),
             Literal::Nil => String::from("nil"),
         }
     }
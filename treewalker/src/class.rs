@@ -1,10 +1,11 @@
-use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
 
 use crate::{
-    ExprKind, Token, Value,
-    error::ReefError,
-    expr::Expr,
-    func::{ReefCallable, ReefFunction},
+    Token, Value, environment::EnvRef, error::ReefError, expr::Expr, func::ReefCallable,
     interpreter::Interpreter,
 };
 
@@ -16,7 +17,17 @@ pub enum ClassKind {
 #[derive(Debug, Clone)]
 pub struct ReefClass {
     pub name: String,
-    pub methods: HashMap<String, ReefFunction>,
+    // undecorated, so `this` is bound before any decorator wraps them
+    pub methods: HashMap<String, Rc<dyn ReefCallable>>,
+    // evaluated decorators per method, innermost last, with the method's name for errors
+    pub decorators: HashMap<String, (Token, Vec<Rc<dyn ReefCallable>>)>,
+    // declared fields and their defaults, in declaration order
+    pub fields: Vec<(String, Expr)>,
+    // sealed classes reject fields they don't declare
+    pub sealed: bool,
+    closure: EnvRef,
+    // lets `call` give instances the same class value the program holds
+    this: Weak<ReefClass>,
 }
 
 pub trait ReefClassAttrs {
@@ -30,10 +41,28 @@ impl ReefClassAttrs for ReefClass {
 }
 
 impl ReefClass {
-    pub fn new(name: String, methods: HashMap<String, ReefFunction>) -> Self {
-        ReefClass { name, methods }
+    pub fn new(
+        name: String,
+        methods: HashMap<String, Rc<dyn ReefCallable>>,
+        decorators: HashMap<String, (Token, Vec<Rc<dyn ReefCallable>>)>,
+        fields: Vec<(String, Expr)>,
+        sealed: bool,
+        closure: EnvRef,
+    ) -> Rc<Self> {
+        Rc::new_cyclic(|this| ReefClass {
+            name,
+            methods,
+            decorators,
+            fields,
+            sealed,
+            closure,
+            this: this.clone(),
+        })
     }
-    pub fn find_method(&self, name: &str) -> Option<&ReefFunction> {
+    pub fn declares(&self, field: &str) -> bool {
+        self.fields.iter().any(|(declared, _)| declared == field)
+    }
+    pub fn find_method(&self, name: &str) -> Option<&Rc<dyn ReefCallable>> {
         self.methods.get(name)
     }
 }
@@ -52,11 +81,18 @@ impl ReefCallable for ReefClass {
         interpreter: &mut Interpreter,
         arguments: Vec<Value>,
     ) -> Result<Value, ReefError> {
-        let instance = ReefInstance::new(self.clone());
-        if let Some(initializer) = self.find_method("init") {
-            initializer
-                .bind(Rc::clone(&instance))
-                .call(interpreter, arguments)?;
+        let class = self
+            .this
+            .upgrade()
+            .expect("a callable class is still alive");
+        let instance = ReefInstance::new(class);
+        // defaults are fresh per instance and set before `init` runs
+        for (field, default) in &self.fields {
+            let value = interpreter.evaluate_in(default, Rc::clone(&self.closure))?;
+            instance.fields.borrow_mut().insert(field.clone(), value);
+        }
+        if let Some(initializer) = instance.bound_method(interpreter, "init")? {
+            initializer.call(interpreter, arguments)?;
         }
 
         Ok(Value::Instance(instance))
@@ -65,46 +101,181 @@ impl ReefCallable for ReefClass {
     fn name(&self) -> &str {
         &self.name
     }
+
+    fn as_class(&self) -> Option<&ReefClass> {
+        Some(self)
+    }
 }
 
-pub type ReefClassRef = Rc<RefCell<ReefClass>>;
+pub type ReefClassRef = Rc<ReefClass>;
 pub type ReefInstanceRef = Rc<ReefInstance>;
 
 #[derive(Debug, Clone)]
 pub struct ReefInstance {
     class: ReefClassRef,
     fields: RefCell<HashMap<String, Value>>,
+    // decorated methods are built once per instance, so decorator state like a memo cache
+    // lasts as long as the instance
+    decorated: RefCell<HashMap<String, Rc<dyn ReefCallable>>>,
 }
 impl ReefInstance {
-    pub fn new(class: ReefClass) -> Rc<Self> {
+    pub fn new(class: ReefClassRef) -> Rc<Self> {
         Rc::new(ReefInstance {
-            class: Rc::new(RefCell::new(class)),
+            class,
             fields: RefCell::new(HashMap::new()),
+            decorated: RefCell::new(HashMap::new()),
         })
     }
-    pub fn get(self: &Rc<Self>, name: &Token) -> Result<Value, ReefError> {
-        let fields = self.fields.borrow();
-        if let Some(value) = fields.get(&name.lexeme) {
-            return Ok(value.clone());
+    pub fn class(&self) -> &ReefClassRef {
+        &self.class
+    }
+    // the language has no inheritance yet; once classes get superclasses this walks their chain
+    pub fn is_instance_of(&self, class: &ReefClass) -> bool {
+        std::ptr::eq(Rc::as_ptr(&self.class), class)
+    }
+    pub fn field(&self, name: &str) -> Option<Value> {
+        self.fields.borrow().get(name).cloned()
+    }
+    pub fn get(
+        self: &Rc<Self>,
+        interpreter: &mut Interpreter,
+        name: &Token,
+    ) -> Result<Value, ReefError> {
+        if let Some(value) = self.field(&name.lexeme) {
+            return Ok(value);
         };
 
-        let methods = self.class.borrow();
-        if let Some(method) = methods.find_method(&name.lexeme) {
-            let bound = method.bind(Rc::clone(self));
-            return Ok(Value::Callable(Rc::new(bound)));
+        if let Some(method) = self.bound_method(interpreter, &name.lexeme)? {
+            return Ok(Value::Callable(method));
         }
 
         Err(ReefError::reef_error_at_line(name, "Undefined property"))
     }
-    pub fn set(&self, name: &Token, value: Value) -> Result<(), ReefError> {
-        self.fields
+    // looks up a class method bound to this instance, without reporting a missing one
+    pub fn bound_method(
+        self: &Rc<Self>,
+        interpreter: &mut Interpreter,
+        name: &str,
+    ) -> Result<Option<Rc<dyn ReefCallable>>, ReefError> {
+        let Some(method) = self.class.find_method(name) else {
+            return Ok(None);
+        };
+        if let Some(decorated) = self.decorated.borrow().get(name) {
+            return Ok(Some(Rc::clone(decorated)));
+        }
+        let bound = method
+            .bind_method(self)
+            .unwrap_or_else(|| Rc::clone(method));
+        let Some((token, decorators)) = self.class.decorators.get(name) else {
+            return Ok(Some(bound));
+        };
+        let decorated = interpreter.apply_decorators(bound, decorators, token)?;
+        self.decorated
             .borrow_mut()
-            .insert(name.lexeme.to_string(), value);
+            .insert(name.to_string(), Rc::clone(&decorated));
+        Ok(Some(decorated))
+    }
+    // public fields sorted by name, since the backing map has no stable order
+    pub fn fields(&self) -> Vec<(String, Value)> {
+        let mut fields: Vec<(String, Value)> = self
+            .fields
+            .borrow()
+            .iter()
+            .filter(|(name, _)| !name.starts_with('#'))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+        fields
+    }
+    pub fn set(&self, name: &Token, value: Value) -> Result<(), ReefError> {
+        self.set_field(name.lexeme.to_string(), value)
+            .map_err(|message| ReefError::reef_runtime_error(name, &message))
+    }
+    pub fn set_field(&self, name: String, value: Value) -> Result<(), String> {
+        if self.class.sealed && !self.class.declares(&name) {
+            return Err(format!(
+                "{} is sealed and doesn't declare '{}'",
+                self.class.name, name
+            ));
+        }
+        self.fields.borrow_mut().insert(name, value);
         Ok(())
     }
 }
 impl ReefClassAttrs for ReefInstance {
     fn to_class_string(&self) -> String {
-        format!("{} instance", self.class.borrow().name)
+        format!("{} instance", self.class.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+
+    #[test]
+    fn declared_fields_get_fresh_defaults() {
+        let interpreter = testing::run(
+            "
+            var start = 10;
+            class Counter {
+                var count = start;
+                var seen = [];
+                var label;
+                bump() { this.count = this.count + 1; this.seen.push(this.count); }
+            }
+            var a = Counter();
+            var b = Counter();
+            a.bump();
+            var state = [a.count, a.seen, b.count, b.seen, a.label];
+            sealed class Point {
+                var x = 0;
+                var y = 0;
+                init(x) { this.x = x; }
+            }
+            var p = Point(3);
+            p.y = 4;
+            var coords = [p.x, p.y];
+            var sealed = \"still a name\";
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("state"), "[11, [11], 10, [], nil]");
+        assert_eq!(render("coords"), "[3, 4]");
+        assert_eq!(render("sealed"), "still a name");
+        assert!(testing::run("sealed class P { var x; } P().z = 1;").is_err());
+        assert!(testing::run("sealed class P { init() { this.typo = 1; } } P();").is_err());
+        assert!(testing::run("sealed class P { var x; } setField(P(), \"y\", 1);").is_err());
+        assert!(testing::run("class P { var x; var x; }").is_err());
+    }
+
+    #[test]
+    fn private_members_stay_behind_this() {
+        let interpreter = testing::run(
+            "
+            class Account {
+                var #balance = 0;
+                deposit(amount) { this.#balance = this.#check(amount) + this.#balance; }
+                balance() { return this.#balance; }
+                #check(amount) { return amount; }
+            }
+            var account = Account();
+            account.deposit(5);
+            var visible = [account.balance(), fields(account), methods(Account), hasField(account, \"#balance\")];
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let visible = globals.get("visible").expect("defined");
+        assert_eq!(
+            interpreter.stringify(&visible),
+            r#"[5, [], ["balance", "deposit"], false]"#
+        );
+        assert!(testing::run("class A { var #x = 1; } A().#x;").is_err());
+        assert!(testing::run("class A { var #x = 1; } var a = A(); a.#x = 2;").is_err());
+        assert!(testing::run("class A { var #x = 1; } getField(A(), \"#x\");").is_err());
+        assert!(testing::run("var #x = 1;").is_err());
+        assert!(testing::run("fun f() { return this.#x; }").is_err());
     }
 }
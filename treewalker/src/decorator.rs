@@ -0,0 +1,278 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use crate::{
+    environment::EnvRef,
+    error::ReefError,
+    expr::Value,
+    func::{NativeFunction, ReefCallable},
+    interpreter::Interpreter,
+};
+
+pub fn define_decorators(globals: &EnvRef) {
+    let decorators = [
+        NativeFunction::new("memoize", 1, |_interpreter, args| {
+            let inner = expect_callable(&args[0], "memoize")?;
+            Ok(Value::Callable(Rc::new(Memoized::new(inner))))
+        }),
+        NativeFunction::new("trace", 1, |_interpreter, args| {
+            let inner = expect_callable(&args[0], "trace")?;
+            Ok(Value::Callable(Rc::new(Traced { inner })))
+        }),
+        NativeFunction::new("deprecated", 1, |_interpreter, args| {
+            let inner = expect_callable(&args[0], "deprecated")?;
+            Ok(Value::Callable(Rc::new(Deprecated {
+                inner,
+                warned: Rc::new(Cell::new(false)),
+            })))
+        }),
+    ];
+    for decorator in decorators {
+        globals
+            .borrow_mut()
+            .define(decorator.name.clone(), Value::Callable(Rc::new(decorator)))
+            .expect("expect decorator to be definable");
+    }
+}
+
+fn expect_callable(value: &Value, decorator: &str) -> Result<Rc<dyn ReefCallable>, ReefError> {
+    match value {
+        Value::Callable(callable) => Ok(Rc::clone(callable)),
+        _ => Err(ReefError::reef_native_error(&format!(
+            "@{} expects a function, got {:?}",
+            decorator, value
+        ))),
+    }
+}
+
+// cache key for primitive arguments; anything else skips the cache
+fn memo_key(args: &[Value]) -> Option<String> {
+    let mut key = String::new();
+    for arg in args {
+        match arg {
+            Value::Int(n) => key.push_str(&format!("|i{}", n)),
+            Value::BigInt(n) => key.push_str(&format!("|i{}", n)),
+            Value::Rational(n) => key.push_str(&format!("|r{}", n)),
+            Value::Number(n) => key.push_str(&format!("|n{}", n.to_bits())),
+            Value::String(s) => key.push_str(&format!("|s{}:{}", s.len(), s)),
+            Value::Boolean(b) => key.push_str(&format!("|b{}", b)),
+            Value::Nil => key.push_str("|nil"),
+            _ => return None,
+        }
+    }
+    Some(key)
+}
+
+#[derive(Debug, Clone)]
+pub struct Memoized {
+    inner: Rc<dyn ReefCallable>,
+    cache: RefCell<HashMap<String, Value>>,
+}
+
+impl Memoized {
+    pub fn new(inner: Rc<dyn ReefCallable>) -> Self {
+        Memoized {
+            inner,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl ReefCallable for Memoized {
+    fn arity(&self) -> usize {
+        self.inner.arity()
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, ReefError> {
+        let key = memo_key(&arguments);
+        if let Some(value) = key
+            .as_ref()
+            .and_then(|k| self.cache.borrow().get(k).cloned())
+        {
+            return Ok(value);
+        }
+        let value = self.inner.call(interpreter, arguments)?;
+        if let Some(key) = key {
+            self.cache.borrow_mut().insert(key, value.clone());
+        }
+        Ok(value)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn to_reef_string(&self) -> String {
+        self.inner.to_reef_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Traced {
+    inner: Rc<dyn ReefCallable>,
+}
+
+impl ReefCallable for Traced {
+    fn arity(&self) -> usize {
+        self.inner.arity()
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, ReefError> {
+        let rendered: Vec<String> = arguments
+            .iter()
+            .map(|arg| interpreter.stringify(arg))
+            .collect();
+        eprintln!("[trace] {}({})", self.name(), rendered.join(", "));
+        let value = self.inner.call(interpreter, arguments)?;
+        eprintln!(
+            "[trace] {} -> {}",
+            self.name(),
+            interpreter.stringify(&value)
+        );
+        Ok(value)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn to_reef_string(&self) -> String {
+        self.inner.to_reef_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Deprecated {
+    inner: Rc<dyn ReefCallable>,
+    warned: Rc<Cell<bool>>,
+}
+
+impl ReefCallable for Deprecated {
+    fn arity(&self) -> usize {
+        self.inner.arity()
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, ReefError> {
+        if !self.warned.replace(true) {
+            eprintln!("Warning: '{}' is deprecated", self.name());
+        }
+        self.inner.call(interpreter, arguments)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn to_reef_string(&self) -> String {
+        self.inner.to_reef_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Value, testing};
+
+    #[test]
+    fn memoize_caches_recursive_calls() {
+        let source = "
+            var calls = 0;
+            @memoize
+            fun fib(n) {
+                calls = calls + 1;
+                if (n < 2) return n;
+                return fib(n - 1) + fib(n - 2);
+            }
+            var result = fib(30);
+        ";
+        let interpreter = testing::run(source).expect("source should run");
+        let globals = interpreter.globals.borrow();
+        assert!(matches!(globals.get("result"), Ok(Value::Int(832040))));
+        assert!(matches!(globals.get("calls"), Ok(Value::Int(31))));
+    }
+
+    #[test]
+    fn decorators_apply_to_functions_and_methods() {
+        let source = "
+            fun twice(f) {
+                fun wrapper(x) { return f(f(x)); }
+                return wrapper;
+            }
+            class Counter {
+                init() { this.step = 5; }
+                @memoize
+                add(x) { return x + this.step; }
+            }
+            @twice
+            fun inc(x) { return x + 1; }
+            var result = inc(1) + Counter().add(1);
+        ";
+        assert!(matches!(testing::eval(source, "result"), Value::Int(9)));
+    }
+
+    #[test]
+    fn memoized_methods_cache_per_instance() {
+        let source = "
+            class C {
+                init(n) { this.n = n; }
+                @memoize
+                get(x) { return this.n + x; }
+            }
+            var results = [];
+            for (var i = 0; i < 5; i = i + 1) {
+                results.push(C(i).get(1));
+            }
+        ";
+        let interpreter = testing::run(source).expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let results = globals.get("results").expect("defined");
+        assert_eq!(interpreter.stringify(&results), "[1, 2, 3, 4, 5]");
+    }
+
+    #[test]
+    fn closure_decorators_keep_this_on_methods() {
+        let source = "
+            var wrapped = 0;
+            fun logged(f) {
+                wrapped = wrapped + 1;
+                fun wrapper(x) { return f(x); }
+                return wrapper;
+            }
+            class A {
+                init(v) { this.v = v; }
+                @logged
+                get(x) { return this.v + x; }
+            }
+            var a = A(10);
+            var results = [a.get(1), a.get(2), A(20).get(1), wrapped];
+        ";
+        let interpreter = testing::run(source).expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let results = globals.get("results").expect("defined");
+        assert_eq!(interpreter.stringify(&results), "[11, 12, 21, 2]");
+    }
+
+    #[test]
+    fn decorator_must_return_callable() {
+        let source = "
+            fun broken(f) { return 1; }
+            @broken
+            fun f() {}
+        ";
+        assert!(testing::run(source).is_err());
+    }
+}
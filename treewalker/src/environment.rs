@@ -1,6 +1,6 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crate::{Token, error::ReefError, expr::Value};
+use crate::{error::ReefError, expr::Value};
 
 pub type EnvRef = Rc<RefCell<Environment>>;
 
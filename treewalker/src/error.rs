@@ -7,9 +7,14 @@ use crate::{
 pub enum ReefError {
     ParseError(String),
     RuntimeError(String),
+    // raised by native functions; the interpreter attaches the call site
+    NativeError(String),
     Return(Value),
+    // raised by `sys.exit`; unwinds the whole script with the given status
+    Exit(i32),
 }
 impl ReefError {
+    #[allow(clippy::self_named_constructors)]
     pub fn reef_error(line: usize, message: &str) -> ReefError {
         ReefError::reef_report(line, "", message)
     }
@@ -35,9 +40,15 @@ impl ReefError {
         eprintln!("Error {:?}: {}", token, message);
         ReefError::RuntimeError(format!("Error {:?}: {}", token, message))
     }
+    pub fn reef_native_error(message: &str) -> ReefError {
+        ReefError::NativeError(message.to_string())
+    }
     pub fn reef_return(value: Value) -> ReefError {
         ReefError::Return(value)
     }
+    pub fn reef_exit(code: i32) -> ReefError {
+        ReefError::Exit(code)
+    }
 }
 
 // TODO: track runtime errors in main Reef struct
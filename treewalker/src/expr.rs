@@ -1,24 +1,49 @@
 #![allow(unused_variables, dead_code)]
 
-use crate::class::{ReefClassRef, ReefInstance, ReefInstanceRef};
-use crate::{
-    Literal, Token, TokenType, error::ReefError, func::ReefCallable, interpreter::Interpreter,
-};
-use std::rc::Rc;
+use crate::bigint::BigInt;
+use crate::class::ReefInstanceRef;
+use crate::map::ReefMap;
+use crate::module::ReefModuleRef;
+use crate::rational::Rational;
+use crate::regex::Regex;
+use crate::set::ReefSet;
+use crate::stdlib::{net::SocketRef, proc::ProcessRef};
+use crate::{Literal, Token, error::ReefError, func::ReefCallable};
+use bytes::Bytes;
+use std::{cell::RefCell, rc::Rc};
 
 #[derive(Debug, Clone)]
 pub enum Value {
     String(String),
+    Bytes(Bytes),
+    Int(i64),
+    BigInt(Rc<BigInt>),
+    Rational(Rc<Rational>),
     Number(f64),
     Boolean(bool),
     Nil,
     Callable(Rc<dyn ReefCallable>),
     Instance(ReefInstanceRef),
+    Module(ReefModuleRef),
+    List(ListRef),
+    Map(MapRef),
+    Set(SetRef),
+    Tuple(Rc<Vec<Value>>),
+    Regex(Rc<Regex>),
+    Process(ProcessRef),
+    Socket(SocketRef),
 }
 
+pub type ListRef = Rc<RefCell<Vec<Value>>>;
+pub type MapRef = Rc<RefCell<ReefMap>>;
+pub type SetRef = Rc<RefCell<ReefSet>>;
+
 impl Value {
     pub fn as_number(&self) -> Result<f64, ReefError> {
         match self {
+            Value::Int(n) => Ok(*n as f64),
+            Value::BigInt(n) => Ok(n.to_f64()),
+            Value::Rational(n) => Ok(n.to_f64()),
             Value::Number(n) => Ok(*n),
             _ => Err(ReefError::reef_general_error(&format!(
                 "Expected number, got {:?}",
@@ -26,6 +51,25 @@ impl Value {
             ))),
         }
     }
+    pub fn as_int(&self) -> Result<i64, ReefError> {
+        match self {
+            Value::Int(n) => Ok(*n),
+            _ => Err(ReefError::reef_general_error(&format!(
+                "Expected integer, got {:?}",
+                self
+            ))),
+        }
+    }
+    // numeric value widened to f64, without reporting an error for non-numbers
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Int(n) => Some(*n as f64),
+            Value::BigInt(n) => Some(n.to_f64()),
+            Value::Rational(n) => Some(n.to_f64()),
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
     pub fn as_string(&self) -> Result<&str, ReefError> {
         match self {
             Value::String(s) => Ok(s),
@@ -44,6 +88,37 @@ impl Value {
             ))),
         }
     }
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Bytes(_) => "bytes",
+            Value::Int(_) => "int",
+            Value::BigInt(_) => "bigint",
+            Value::Rational(_) => "rational",
+            Value::Number(_) => "float",
+            Value::Boolean(_) => "bool",
+            Value::Nil => "nil",
+            Value::Callable(_) => "function",
+            Value::Instance(_) => "instance",
+            Value::Module(_) => "module",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::Set(_) => "set",
+            Value::Tuple(_) => "tuple",
+            Value::Regex(_) => "regex",
+            Value::Process(_) => "process",
+            Value::Socket(_) => "socket",
+        }
+    }
+    pub fn new_list(values: Vec<Value>) -> Value {
+        Value::List(Rc::new(RefCell::new(values)))
+    }
+    pub fn new_map(map: ReefMap) -> Value {
+        Value::Map(Rc::new(RefCell::new(map)))
+    }
+    pub fn new_set(set: ReefSet) -> Value {
+        Value::Set(Rc::new(RefCell::new(set)))
+    }
     pub fn is_truthy(&self) -> bool {
         match self {
             Value::Nil => false,
@@ -78,9 +153,31 @@ pub enum ExprKind {
     Grouping {
         expression: Expr,
     },
+    Tuple {
+        elements: Vec<Expr>,
+    },
+    Index {
+        object: Expr,
+        bracket: Token,
+        index: Expr,
+    },
+    List {
+        elements: Vec<Expr>,
+    },
+    Map {
+        brace: Token,
+        entries: Vec<(Expr, Expr)>,
+    },
+    SetLiteral {
+        brace: Token,
+        elements: Vec<Expr>,
+    },
     Literal {
         value: Literal,
     },
+    Placeholder {
+        token: Token,
+    },
     Logical {
         left: Expr,
         operator: Token,
@@ -91,6 +188,12 @@ pub enum ExprKind {
         name: Token,
         value: Expr,
     },
+    SetIndex {
+        object: Expr,
+        bracket: Token,
+        index: Expr,
+        value: Expr,
+    },
     Super {
         keyword: Token,
         method: Token,
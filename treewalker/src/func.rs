@@ -1,8 +1,8 @@
-use crate::class::ReefInstanceRef;
+use crate::class::{ReefClass, ReefInstanceRef};
 use crate::environment::{EnvRef, Environment};
 use crate::expr::Value;
 use crate::stmt::StmtKind;
-use crate::{Token, error::ReefError, interpreter::Interpreter};
+use crate::{error::ReefError, interpreter::Interpreter};
 use std::fmt;
 use std::rc::Rc;
 
@@ -53,9 +53,56 @@ impl ReefFunction {
 pub struct NativeFunction {
     pub name: String,
     pub arity: usize,
+    pub variadic: bool,
     pub func: InterpreterFn,
 }
 
+impl NativeFunction {
+    pub fn new(name: &str, arity: usize, func: InterpreterFn) -> Self {
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            variadic: false,
+            func,
+        }
+    }
+    // accepts `arity` or more arguments
+    pub fn variadic(name: &str, arity: usize, func: InterpreterFn) -> Self {
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            variadic: true,
+            func,
+        }
+    }
+}
+
+// native method bound to its receiver, e.g. `"abc".upper`; the receiver is passed as the first argument
+#[derive(Debug, Clone)]
+pub struct NativeMethod {
+    pub receiver: Value,
+    pub method: NativeFunction,
+}
+
+impl NativeMethod {
+    pub fn new(receiver: Value, method: NativeFunction) -> Self {
+        NativeMethod { receiver, method }
+    }
+}
+
+// callable produced by `f(_, x)`; the `None` slots are filled by the eventual call
+#[derive(Debug, Clone)]
+pub struct PartialFunction {
+    pub callee: Rc<dyn ReefCallable>,
+    pub arguments: Vec<Option<Value>>,
+}
+
+impl PartialFunction {
+    pub fn new(callee: Rc<dyn ReefCallable>, arguments: Vec<Option<Value>>) -> Self {
+        PartialFunction { callee, arguments }
+    }
+}
+
 pub trait ReefCallable: fmt::Debug {
     fn arity(&self) -> usize;
     fn call(
@@ -64,9 +111,20 @@ pub trait ReefCallable: fmt::Debug {
         arguments: Vec<Value>,
     ) -> Result<Value, ReefError>;
     fn name(&self) -> &str;
+    fn is_variadic(&self) -> bool {
+        false
+    }
+    // returns the callable with `this` bound, or None if binding doesn't apply
+    fn bind_method(&self, _instance: &ReefInstanceRef) -> Option<Rc<dyn ReefCallable>> {
+        None
+    }
     fn to_reef_string(&self) -> String {
         format!("<fn {}>", self.name())
     }
+    // the class behind a class value, for reflection
+    fn as_class(&self) -> Option<&ReefClass> {
+        None
+    }
 }
 
 impl ReefCallable for NativeFunction {
@@ -74,6 +132,10 @@ impl ReefCallable for NativeFunction {
         self.arity
     }
 
+    fn is_variadic(&self) -> bool {
+        self.variadic
+    }
+
     fn call(
         &self,
         interpreter: &mut Interpreter,
@@ -113,13 +175,10 @@ impl ReefCallable for ReefFunction {
                         self.closure.borrow().get_at(&0, "this")
                     }
                     Err(ReefError::Return(val)) => Ok(val),
-                    other => {
-                        if self.is_initializer {
-                            self.closure.borrow().get_at(&0, "this")
-                        } else {
-                            other.map(|_| Value::Nil)
-                        }
-                    }
+                    // errors inside `init` propagate like any other call's
+                    Err(e) => Err(e),
+                    Ok(()) if self.is_initializer => self.closure.borrow().get_at(&0, "this"),
+                    Ok(()) => Ok(Value::Nil),
                 }
             }
             _ => unreachable!(),
@@ -132,4 +191,64 @@ impl ReefCallable for ReefFunction {
             _ => unreachable!(),
         }
     }
+
+    fn bind_method(&self, instance: &ReefInstanceRef) -> Option<Rc<dyn ReefCallable>> {
+        Some(Rc::new(self.bind(Rc::clone(instance))))
+    }
+}
+
+impl ReefCallable for NativeMethod {
+    fn arity(&self) -> usize {
+        self.method.arity
+    }
+
+    fn is_variadic(&self) -> bool {
+        self.method.variadic
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, ReefError> {
+        let mut with_receiver = Vec::with_capacity(arguments.len() + 1);
+        with_receiver.push(self.receiver.clone());
+        with_receiver.extend(arguments);
+        (self.method.func)(interpreter, with_receiver)
+    }
+
+    fn name(&self) -> &str {
+        &self.method.name
+    }
+}
+
+impl ReefCallable for PartialFunction {
+    fn arity(&self) -> usize {
+        self.arguments.iter().filter(|arg| arg.is_none()).count()
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, ReefError> {
+        let mut supplied = arguments.into_iter();
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|arg| match arg {
+                Some(value) => value.clone(),
+                None => supplied.next().unwrap_or(Value::Nil),
+            })
+            .collect();
+        self.callee.call(interpreter, arguments)
+    }
+
+    fn name(&self) -> &str {
+        self.callee.name()
+    }
+
+    fn to_reef_string(&self) -> String {
+        format!("<partial fn {}>", self.name())
+    }
 }
@@ -1,76 +1,294 @@
 use std::{
+    cmp::Ordering,
     collections::HashMap,
     rc::Rc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
+use crate::func::{NativeFunction, NativeMethod, PartialFunction, ReefCallable, ReefFunction};
+use crate::stdlib::{bytes, list, map, net, proc, random::Rng, re, set, string, tuple};
 use crate::{
     Literal, Token, TokenType,
     class::{ReefClass, ReefClassAttrs},
+    decorator,
     environment::{EnvRef, Environment},
     error::ReefError,
     expr::{Expr, ExprKind, Value},
+    map::ReefMap,
+    number,
+    set::ReefSet,
+    stdlib,
     stmt::StmtKind,
 };
-use crate::{
-    class::ReefInstance,
-    func::{NativeFunction, ReefCallable, ReefFunction},
-};
 
+// containers compare by contents; instances, functions, modules and resources by identity
 fn is_equal(a: &Value, b: &Value) -> bool {
+    equal_nested(a, b, &mut Vec::new())
+}
+
+// pairs already being compared are assumed equal, so cyclic lists and maps terminate
+fn equal_nested(a: &Value, b: &Value, seen: &mut Vec<(*const (), *const ())>) -> bool {
+    let pair = match (a, b) {
+        (Value::List(l), Value::List(r)) => {
+            Some((Rc::as_ptr(l) as *const (), Rc::as_ptr(r) as *const ()))
+        }
+        (Value::Map(l), Value::Map(r)) => {
+            Some((Rc::as_ptr(l) as *const (), Rc::as_ptr(r) as *const ()))
+        }
+        _ => None,
+    };
+    if let Some(pair) = pair {
+        if pair.0 == pair.1 || seen.contains(&pair) {
+            return true;
+        }
+        seen.push(pair);
+    }
+    let equal = match (a, b) {
+        (Value::List(l), Value::List(r)) => {
+            let (l, r) = (l.borrow(), r.borrow());
+            l.len() == r.len()
+                && l.iter()
+                    .zip(r.iter())
+                    .all(|(l, r)| equal_nested(l, r, seen))
+        }
+        (Value::Tuple(l), Value::Tuple(r)) => {
+            l.len() == r.len()
+                && l.iter()
+                    .zip(r.iter())
+                    .all(|(l, r)| equal_nested(l, r, seen))
+        }
+        // entry order doesn't matter, only that each key maps to an equal value
+        (Value::Map(l), Value::Map(r)) => {
+            let (l, r) = (l.borrow(), r.borrow());
+            l.len() == r.len()
+                && l.iter().all(|(key, l)| match r.get_value(key) {
+                    Ok(Some(r)) => equal_nested(l, r, seen),
+                    _ => false,
+                })
+        }
+        (Value::Set(l), Value::Set(r)) => {
+            let (l, r) = (l.borrow(), r.borrow());
+            l.len() == r.len() && l.iter().all(|member| r.contains(member) == Ok(true))
+        }
+        _ => is_equal_scalar(a, b),
+    };
+    if pair.is_some() {
+        seen.pop();
+    }
+    equal
+}
+
+fn is_equal_scalar(a: &Value, b: &Value) -> bool {
     match (a, b) {
-        (Value::Number(l), Value::Number(r)) => l == r,
+        (Value::Int(l), Value::Int(r)) => l == r,
+        (
+            Value::Int(_) | Value::BigInt(_) | Value::Rational(_) | Value::Number(_),
+            Value::Int(_) | Value::BigInt(_) | Value::Rational(_) | Value::Number(_),
+        ) => number::compare(a, b) == Some(Some(Ordering::Equal)),
         (Value::String(l), Value::String(r)) => l == r,
+        (Value::Bytes(l), Value::Bytes(r)) => l == r,
         (Value::Boolean(l), Value::Boolean(r)) => l == r,
         (Value::Nil, Value::Nil) => true,
+        (Value::Instance(l), Value::Instance(r)) => Rc::ptr_eq(l, r),
+        (Value::Callable(l), Value::Callable(r)) => {
+            Rc::as_ptr(l) as *const () == Rc::as_ptr(r) as *const ()
+        }
+        (Value::Module(l), Value::Module(r)) => Rc::ptr_eq(l, r),
+        (Value::Regex(l), Value::Regex(r)) => Rc::ptr_eq(l, r),
+        (Value::Process(l), Value::Process(r)) => Rc::ptr_eq(l, r),
+        (Value::Socket(l), Value::Socket(r)) => Rc::ptr_eq(l, r),
         (_, Value::Nil) => false,
         (Value::Nil, _) => false,
         _ => false,
     }
 }
 
+// sets and maps look the needle up by key, lists compare with `==`, strings search substrings
+fn evaluate_membership(
+    operator: &Token,
+    needle: &Value,
+    haystack: &Value,
+) -> Result<Value, ReefError> {
+    let found = match (needle, haystack) {
+        (_, Value::Set(members)) => members.borrow().contains(needle),
+        (_, Value::Map(entries)) => entries
+            .borrow()
+            .get_value(needle)
+            .map(|found| found.is_some()),
+        (_, Value::List(items)) => Ok(items.borrow().iter().any(|item| is_equal(needle, item))),
+        (_, Value::Tuple(items)) => Ok(items.iter().any(|item| is_equal(needle, item))),
+        (Value::String(needle), Value::String(text)) => Ok(text.contains(needle.as_str())),
+        (_, Value::String(_)) => Err(format!(
+            "can't look for a {} in a string",
+            needle.type_name()
+        )),
+        _ => Err(format!(
+            "can't look for values in a {}",
+            haystack.type_name()
+        )),
+    };
+    found
+        .map(Value::Boolean)
+        .map_err(|message| ReefError::reef_runtime_error(operator, &message))
+}
+
+fn evaluate_arithmetic(operator: &Token, left: &Value, right: &Value) -> Result<Value, ReefError> {
+    number::arithmetic(operator.token_type, left, right)
+        .map_err(|message| ReefError::reef_runtime_error(operator, &message))
+}
+
+fn evaluate_comparison(operator: &Token, left: &Value, right: &Value) -> Result<Value, ReefError> {
+    let ordering = number::compare(left, right)
+        .ok_or_else(|| ReefError::reef_runtime_error(operator, "Binary evaluation error"))?;
+    let result = match (operator.token_type, ordering) {
+        (TokenType::Greater, Some(ordering)) => ordering.is_gt(),
+        (TokenType::GreaterEqual, Some(ordering)) => ordering.is_ge(),
+        (TokenType::Less, Some(ordering)) => ordering.is_lt(),
+        (TokenType::LessEqual, Some(ordering)) => ordering.is_le(),
+        _ => false,
+    };
+    Ok(Value::Boolean(result))
+}
+
+fn stringify_float(n: f64) -> String {
+    if n.is_finite() && n.fract() == 0.0 && n.abs() < 1e16 {
+        format!("{:.1}", n)
+    } else {
+        n.to_string()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Interpreter {
     pub globals: EnvRef,
     pub environment: EnvRef,
     pub locals: HashMap<*const ExprKind, usize>,
+    // backs the `random` module; replaced by `random.seed`
+    pub rng: Rng,
 }
 
 impl Interpreter {
     pub fn new() -> Self {
+        Interpreter::with_args(Vec::new())
+    }
+
+    // `args` are the script arguments exposed as `sys.args`
+    pub fn with_args(args: Vec<String>) -> Self {
         let globals = Environment::new_ref(None);
-        let clock = NativeFunction {
-            name: "reef_clock".to_string(),
-            arity: 0,
-            func: |_interpreter, _args| {
-                let time = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs_f64();
-                Ok(Value::Number(time))
-            },
-        };
+        let clock = NativeFunction::new("reef_clock", 0, |_interpreter, _args| {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64();
+            Ok(Value::Number(time))
+        });
 
         globals
             .borrow_mut()
             .define("clock".to_string(), Value::Callable(Rc::new(clock)))
             .expect("expect clock function to be definable");
+        decorator::define_decorators(&globals);
+        number::define_natives(&globals);
+        stdlib::collections::define_natives(&globals);
+        stdlib::format::define_natives(&globals);
+        stdlib::io::define_natives(&globals);
+        stdlib::reflect::define_natives(&globals);
+        stdlib::set::define_natives(&globals);
+        stdlib::tuple::define_natives(&globals);
+        stdlib::define_modules(&globals, args);
 
         Interpreter {
             environment: Rc::clone(&globals),
             globals,
             locals: HashMap::new(),
+            rng: Rng::from_time(),
         }
     }
 
     pub fn stringify(&self, value: &Value) -> String {
+        self.stringify_nested(value, &mut Vec::new())
+    }
+
+    // strings keep their quotes, the way they print inside a list or map
+    pub fn repr(&self, value: &Value) -> String {
+        self.stringify_element(value, &mut Vec::new())
+    }
+
+    // `seen` holds the containers being printed so a self-containing one prints as `[...]`
+    fn stringify_nested(&self, value: &Value, seen: &mut Vec<*const ()>) -> String {
         match value {
-            Value::Number(n) => n.to_string(),
+            Value::List(items) => {
+                let pointer = Rc::as_ptr(items) as *const ();
+                if seen.contains(&pointer) {
+                    return String::from("[...]");
+                }
+                seen.push(pointer);
+                let rendered: Vec<String> = items
+                    .borrow()
+                    .iter()
+                    .map(|item| self.stringify_element(item, seen))
+                    .collect();
+                seen.pop();
+                format!("[{}]", rendered.join(", "))
+            }
+            Value::Map(entries) => {
+                let pointer = Rc::as_ptr(entries) as *const ();
+                if seen.contains(&pointer) {
+                    return String::from("{...}");
+                }
+                seen.push(pointer);
+                let rendered: Vec<String> = entries
+                    .borrow()
+                    .iter()
+                    .map(|(key, item)| {
+                        let key = self.stringify_element(key, seen);
+                        format!("{}: {}", key, self.stringify_element(item, seen))
+                    })
+                    .collect();
+                seen.pop();
+                format!("{{{}}}", rendered.join(", "))
+            }
+            Value::Tuple(items) => {
+                let rendered: Vec<String> = items
+                    .iter()
+                    .map(|item| self.stringify_element(item, seen))
+                    .collect();
+                match rendered.as_slice() {
+                    [only] => format!("({},)", only),
+                    _ => format!("({})", rendered.join(", ")),
+                }
+            }
+            // members are never containers, so a set can't reach itself
+            Value::Set(members) => {
+                let rendered: Vec<String> = members
+                    .borrow()
+                    .iter()
+                    .map(|member| self.stringify_element(member, seen))
+                    .collect();
+                format!("#{{{}}}", rendered.join(", "))
+            }
+            Value::Int(n) => n.to_string(),
+            Value::BigInt(n) => n.to_string(),
+            Value::Rational(n) => n.to_string(),
+            Value::Number(n) => stringify_float(*n),
             Value::Boolean(n) => n.to_string(),
             Value::String(n) => n.to_string(),
+            Value::Bytes(n) => bytes::display(n),
             Value::Nil => String::from("nil"),
             Value::Callable(n) => n.to_reef_string(),
             Value::Instance(n) => n.to_class_string(),
+            Value::Module(n) => format!("<module {}>", n.name),
+            Value::Regex(n) => format!("<regex {}>", n),
+            Value::Process(n) => n.borrow().to_string(),
+            Value::Socket(n) => n.borrow().to_string(),
+        }
+    }
+
+    // strings inside containers are quoted so `["a, b"]` and `["a", "b"]` print differently
+    fn stringify_element(&self, value: &Value, seen: &mut Vec<*const ()>) -> String {
+        match value {
+            Value::String(s) => format!("{:?}", s),
+            _ => self.stringify_nested(value, seen),
         }
     }
 
@@ -84,64 +302,20 @@ impl Interpreter {
         let right_val = self.evaluate(right)?;
         match operator.token_type {
             TokenType::Plus => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Number(l + r)),
                 (Value::String(l), Value::String(r)) => Ok(Value::String(format!("{}{}", l, r))),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
-            },
-            TokenType::Minus => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Number(l - r)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
-            },
-            TokenType::Star => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Number(l * r)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
-            },
-            TokenType::Slash => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Number(l / r)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
+                (Value::Bytes(l), Value::Bytes(r)) => Ok(Value::Bytes(bytes::concat(l, r))),
+                _ => evaluate_arithmetic(operator, &left_val, &right_val),
             },
+            TokenType::Minus | TokenType::Star | TokenType::Slash => {
+                evaluate_arithmetic(operator, &left_val, &right_val)
+            }
             TokenType::EqualEqual => Ok(Value::Boolean(is_equal(&left_val, &right_val))),
             TokenType::BangEqual => Ok(Value::Boolean(!is_equal(&left_val, &right_val))),
-            TokenType::GreaterEqual => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Boolean(l >= r)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
-            },
-            TokenType::Greater => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Boolean(l > r)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
-            },
-            TokenType::LessEqual => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Boolean(l <= r)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
-            },
-            TokenType::Less => match (&left_val, &right_val) {
-                (Value::Number(l), Value::Number(r)) => Ok(Value::Boolean(l < r)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Binary evaluation error",
-                )),
-            },
+            TokenType::GreaterEqual
+            | TokenType::Greater
+            | TokenType::LessEqual
+            | TokenType::Less => evaluate_comparison(operator, &left_val, &right_val),
+            TokenType::In => evaluate_membership(operator, &left_val, &right_val),
             _ => Err(ReefError::reef_runtime_error(
                 operator,
                 "Binary evaluation error",
@@ -152,13 +326,8 @@ impl Interpreter {
     fn evaluate_unary(&mut self, operator: &Token, right: &Expr) -> Result<Value, ReefError> {
         let right_val = self.evaluate(right)?;
         match operator.token_type {
-            TokenType::Minus => match right_val {
-                Value::Number(n) => Ok(Value::Number(-n)),
-                _ => Err(ReefError::reef_runtime_error(
-                    operator,
-                    "Operand must be a number",
-                )),
-            },
+            TokenType::Minus => number::negate(&right_val)
+                .ok_or_else(|| ReefError::reef_runtime_error(operator, "Operand must be a number")),
             TokenType::Bang => Ok(Value::Boolean(!right_val.is_truthy())),
             _ => Err(ReefError::reef_runtime_error(
                 operator,
@@ -170,6 +339,9 @@ impl Interpreter {
     fn evaluate_literal(&self, value: &Literal) -> Result<Value, ReefError> {
         Ok(match value {
             Literal::String(s) => Value::String(s.clone()),
+            Literal::Bytes(b) => Value::Bytes(b.clone()),
+            Literal::Int(n) => Value::Int(*n),
+            Literal::BigInt(n) => Value::BigInt(Rc::new(n.clone())),
             Literal::Number(n) => Value::Number(*n),
             Literal::Boolean(b) => Value::Boolean(*b),
             Literal::Nil => Value::Nil,
@@ -242,6 +414,10 @@ impl Interpreter {
             } => self.evaluate_call_expr(callee, token, arguments),
             ExprKind::Grouping { expression } => self.evaluate(expression),
             ExprKind::Literal { value } => self.evaluate_literal(value),
+            ExprKind::Placeholder { token } => Err(ReefError::reef_runtime_error(
+                token,
+                "'_' is only allowed as a call argument",
+            )),
             ExprKind::Logical {
                 left,
                 operator,
@@ -250,13 +426,54 @@ impl Interpreter {
             ExprKind::Unary { operator, right } => self.evaluate_unary(operator, right),
             ExprKind::Variable { name } => self.evaluate_variable(name, expr),
             ExprKind::None => Ok(Value::Nil),
-            ExprKind::Get { name, object } => match self.evaluate(object)? {
-                Value::Instance(obj) => obj.get(name),
-                _ => Err(ReefError::reef_runtime_error(
-                    name,
-                    "only instances have properties",
-                )),
-            },
+            ExprKind::Get { name, object } => {
+                let object = self.evaluate(object)?;
+                self.get_property(object, name)
+            }
+            ExprKind::List { elements } => {
+                let mut values = Vec::new();
+                for element in elements {
+                    values.push(self.evaluate(element)?);
+                }
+                Ok(Value::new_list(values))
+            }
+            ExprKind::Map { brace, entries } => {
+                let mut map = ReefMap::new();
+                for (key, value) in entries {
+                    let key = self.evaluate(key)?;
+                    let value = self.evaluate(value)?;
+                    map.insert_value(key, value)
+                        .map_err(|message| ReefError::reef_runtime_error(brace, &message))?;
+                }
+                Ok(Value::new_map(map))
+            }
+            ExprKind::Tuple { elements } => {
+                let mut values = Vec::new();
+                for element in elements {
+                    values.push(self.evaluate(element)?);
+                }
+                Ok(Value::Tuple(Rc::new(values)))
+            }
+            ExprKind::SetLiteral { brace, elements } => {
+                let mut set = ReefSet::new();
+                for element in elements {
+                    let element = self.evaluate(element)?;
+                    set.insert(element)
+                        .map_err(|message| ReefError::reef_runtime_error(brace, &message))?;
+                }
+                Ok(Value::new_set(set))
+            }
+            ExprKind::Index {
+                object,
+                bracket,
+                index,
+            } => self.evaluate_index(object, bracket, index),
+            ExprKind::SetIndex {
+                object,
+                bracket,
+                index,
+                value,
+            } => self.evaluate_set_index(object, bracket, index, value),
             ExprKind::Set {
                 object,
                 name,
@@ -277,6 +494,128 @@ impl Interpreter {
         }
     }
 
+    fn get_property(&mut self, object: Value, name: &Token) -> Result<Value, ReefError> {
+        let method = match &object {
+            Value::Instance(obj) => return obj.get(self, name),
+            Value::Module(module) => return module.get(name),
+            Value::String(_) => string::method(&name.lexeme),
+            Value::Bytes(_) => bytes::method(&name.lexeme),
+            Value::List(_) => list::method(&name.lexeme),
+            Value::Map(_) => map::method(&name.lexeme),
+            Value::Set(_) => set::method(&name.lexeme),
+            Value::Tuple(_) => tuple::method(&name.lexeme),
+            Value::Regex(_) => re::method(&name.lexeme),
+            Value::Process(_) => proc::method(&name.lexeme),
+            Value::Socket(_) => net::method(&name.lexeme),
+            _ => {
+                return Err(ReefError::reef_runtime_error(
+                    name,
+                    "only instances have properties",
+                ));
+            }
+        };
+        match method {
+            Some(method) => Ok(Value::Callable(Rc::new(NativeMethod::new(object, method)))),
+            None => Err(ReefError::reef_runtime_error(
+                name,
+                &format!("{} has no method '{}'", object.type_name(), name.lexeme),
+            )),
+        }
+    }
+
+    fn evaluate_index(
+        &mut self,
+        object: &Expr,
+        bracket: &Token,
+        index: &Expr,
+    ) -> Result<Value, ReefError> {
+        let object = self.evaluate(object)?;
+        let index = match (&object, self.evaluate(index)?) {
+            (Value::Map(entries), key) => {
+                let entries = entries.borrow();
+                let found = entries
+                    .get_value(&key)
+                    .map_err(|message| ReefError::reef_runtime_error(bracket, &message))?;
+                return found.cloned().ok_or_else(|| {
+                    let message = format!("undefined key {}", self.repr(&key));
+                    ReefError::reef_runtime_error(bracket, &message)
+                });
+            }
+            (_, Value::Int(n)) => n,
+            _ => {
+                return Err(ReefError::reef_runtime_error(
+                    bracket,
+                    "index must be an integer",
+                ));
+            }
+        };
+        let out_of_range = || ReefError::reef_runtime_error(bracket, "index out of range");
+        match object {
+            Value::List(items) => {
+                let items = items.borrow();
+                let position =
+                    stdlib::normalize_index(index, items.len()).ok_or_else(out_of_range)?;
+                Ok(items[position].clone())
+            }
+            Value::Tuple(items) => {
+                let position =
+                    stdlib::normalize_index(index, items.len()).ok_or_else(out_of_range)?;
+                Ok(items[position].clone())
+            }
+            Value::String(s) => {
+                let position =
+                    stdlib::normalize_index(index, s.chars().count()).ok_or_else(out_of_range)?;
+                let c = s.chars().nth(position).expect("position is in range");
+                Ok(Value::String(c.to_string()))
+            }
+            Value::Bytes(buf) => {
+                let position =
+                    stdlib::normalize_index(index, buf.len()).ok_or_else(out_of_range)?;
+                Ok(Value::Int(buf[position] as i64))
+            }
+            _ => Err(ReefError::reef_runtime_error(
+                bracket,
+                "only lists, tuples, maps, strings and bytes can be indexed",
+            )),
+        }
+    }
+
+    fn evaluate_set_index(
+        &mut self,
+        object: &Expr,
+        bracket: &Token,
+        index: &Expr,
+        value: &Expr,
+    ) -> Result<Value, ReefError> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+        match (object, index) {
+            (Value::List(items), Value::Int(index)) => {
+                let mut items = items.borrow_mut();
+                let position = stdlib::normalize_index(index, items.len())
+                    .ok_or_else(|| ReefError::reef_runtime_error(bracket, "index out of range"))?;
+                items[position] = value.clone();
+                Ok(value)
+            }
+            (Value::Map(entries), key) => {
+                entries
+                    .borrow_mut()
+                    .insert_value(key, value.clone())
+                    .map_err(|message| ReefError::reef_runtime_error(bracket, &message))?;
+                Ok(value)
+            }
+            (Value::List(_), _) => Err(ReefError::reef_runtime_error(
+                bracket,
+                "index must be an integer",
+            )),
+            _ => Err(ReefError::reef_runtime_error(
+                bracket,
+                "only lists and maps support index assignment",
+            )),
+        }
+    }
+
     fn evaluate_call_expr(
         &mut self,
         callee: &Expr,
@@ -284,21 +623,41 @@ impl Interpreter {
         arguments: &Vec<Expr>,
     ) -> Result<Value, ReefError> {
         let callee_val = self.evaluate(callee)?;
-        let mut arguments_val: Vec<Value> = Vec::new();
+        let mut arguments_val: Vec<Option<Value>> = Vec::new();
         for arg in arguments {
-            arguments_val.push(self.evaluate(arg)?);
+            match arg.as_ref() {
+                ExprKind::Placeholder { .. } => arguments_val.push(None),
+                _ => arguments_val.push(Some(self.evaluate(arg)?)),
+            }
         }
         match callee_val {
             Value::Callable(callable) => {
                 let expected_len = callable.arity();
                 let actual_len = arguments_val.len();
-                if expected_len != actual_len {
+                if callable.is_variadic() && actual_len < expected_len {
+                    return Err(ReefError::reef_runtime_error(
+                        token,
+                        &format!(
+                            "Expected at least {} args, got {} args",
+                            expected_len, actual_len
+                        ),
+                    ));
+                }
+                if !callable.is_variadic() && expected_len != actual_len {
                     return Err(ReefError::reef_runtime_error(
                         token,
                         &format!("Expected: {} args, got {} args", expected_len, actual_len),
                     ));
                 }
-                callable.call(self, arguments_val)
+                if arguments_val.iter().any(Option::is_none) {
+                    let partial = PartialFunction::new(callable, arguments_val);
+                    return Ok(Value::Callable(Rc::new(partial)));
+                }
+                self.call_value(
+                    &callable,
+                    arguments_val.into_iter().flatten().collect(),
+                    token,
+                )
             }
             _ => Err(ReefError::reef_runtime_error(
                 token,
@@ -307,6 +666,21 @@ impl Interpreter {
         }
     }
 
+    // calls `callable`, reporting errors raised by natives at `token`
+    pub fn call_value(
+        &mut self,
+        callable: &Rc<dyn ReefCallable>,
+        arguments: Vec<Value>,
+        token: &Token,
+    ) -> Result<Value, ReefError> {
+        match callable.call(self, arguments) {
+            Err(ReefError::NativeError(message)) => {
+                Err(ReefError::reef_runtime_error(token, &message))
+            }
+            result => result,
+        }
+    }
+
     fn execute_expression(&mut self, expr: &Expr) -> Result<(), ReefError> {
         self.evaluate(expr)?;
         Ok(())
@@ -347,6 +721,14 @@ impl Interpreter {
         result
     }
 
+    // evaluates in another scope, e.g. field defaults in the scope their class was declared in
+    pub fn evaluate_in(&mut self, expr: &Expr, environment: EnvRef) -> Result<Value, ReefError> {
+        let previous = std::mem::replace(&mut self.environment, environment);
+        let result = self.evaluate(expr);
+        self.environment = previous;
+        result
+    }
+
     fn execute_if(
         &mut self,
         condition: &Expr,
@@ -361,14 +743,69 @@ impl Interpreter {
         Ok(())
     }
 
-    fn execute_func(&mut self, stmt: StmtKind, name: &Token) -> Result<(), ReefError> {
+    fn execute_func(
+        &mut self,
+        stmt: StmtKind,
+        name: &Token,
+        decorators: &[Expr],
+    ) -> Result<(), ReefError> {
         let function = ReefFunction::new(stmt.clone(), Rc::clone(&self.environment))?;
+        let function = self.decorate(Rc::new(function), name, decorators)?;
         self.environment
             .borrow_mut()
-            .define(name.lexeme.clone(), Value::Callable(Rc::new(function)))?;
+            .define(name.lexeme.clone(), Value::Callable(function))?;
         Ok(())
     }
 
+    fn decorate(
+        &mut self,
+        function: Rc<dyn ReefCallable>,
+        name: &Token,
+        decorators: &[Expr],
+    ) -> Result<Rc<dyn ReefCallable>, ReefError> {
+        let decorators = self.evaluate_decorators(name, decorators)?;
+        self.apply_decorators(function, &decorators, name)
+    }
+
+    fn evaluate_decorators(
+        &mut self,
+        name: &Token,
+        decorators: &[Expr],
+    ) -> Result<Vec<Rc<dyn ReefCallable>>, ReefError> {
+        decorators
+            .iter()
+            .map(|decorator| match self.evaluate(decorator)? {
+                Value::Callable(callable) if callable.arity() == 1 => Ok(callable),
+                _ => Err(ReefError::reef_runtime_error(
+                    name,
+                    "decorator must be a callable taking one argument",
+                )),
+            })
+            .collect()
+    }
+
+    // applies decorators innermost first, so `@a @b fun f` binds `a(b(f))`
+    pub fn apply_decorators(
+        &mut self,
+        function: Rc<dyn ReefCallable>,
+        decorators: &[Rc<dyn ReefCallable>],
+        name: &Token,
+    ) -> Result<Rc<dyn ReefCallable>, ReefError> {
+        let mut decorated = function;
+        for decorator in decorators.iter().rev() {
+            decorated = match self.call_value(decorator, vec![Value::Callable(decorated)], name)? {
+                Value::Callable(callable) => callable,
+                _ => {
+                    return Err(ReefError::reef_runtime_error(
+                        name,
+                        "decorator must return a callable",
+                    ));
+                }
+            };
+        }
+        Ok(decorated)
+    }
+
     fn execute_while(&mut self, condition: &Expr, body: &StmtKind) -> Result<(), ReefError> {
         while self.evaluate(condition)?.is_truthy() {
             self.execute(body)?;
@@ -392,34 +829,55 @@ impl Interpreter {
             } => self.execute_if(condition, then_branch, else_branch)?,
             StmtKind::While { condition, body } => self.execute_while(condition, body)?,
             StmtKind::Function {
-                name,
-                parameters: _,
-                body: _,
-            } => self.execute_func(stmt.clone(), name)?,
+                name, decorators, ..
+            } => self.execute_func(stmt.clone(), name, decorators)?,
             StmtKind::Return { keyword: _, value } => {
                 let final_value = self.evaluate(value)?;
                 Err(ReefError::reef_return(final_value))?
             }
-            StmtKind::Class { name, methods } => {
+            StmtKind::Class {
+                name,
+                methods,
+                fields,
+                sealed,
+            } => {
                 self.environment
                     .borrow_mut()
                     .define(name.lexeme.to_string(), Value::Nil)?;
-                let mut class_methods: HashMap<String, ReefFunction> = HashMap::new();
+                let mut class_methods: HashMap<String, Rc<dyn ReefCallable>> = HashMap::new();
+                let mut class_decorators = HashMap::new();
                 for method in methods {
-                    match method {
-                        StmtKind::Function { name, .. } => {
-                            let is_init = name.lexeme == "init";
-                            let mut function =
-                                ReefFunction::new(method.clone(), Rc::clone(&self.environment))?;
-                            function.is_initializer = is_init;
-                            class_methods.insert(name.lexeme.clone(), function);
+                    if let StmtKind::Function {
+                        name, decorators, ..
+                    } = method
+                    {
+                        let is_init = name.lexeme == "init";
+                        let mut function =
+                            ReefFunction::new(method.clone(), Rc::clone(&self.environment))?;
+                        function.is_initializer = is_init;
+                        // instances apply these to their own bound copy of the method
+                        if !decorators.is_empty() {
+                            let decorators = self.evaluate_decorators(name, decorators)?;
+                            class_decorators
+                                .insert(name.lexeme.clone(), (name.clone(), decorators));
                         }
-                        _ => {}
+                        class_methods.insert(name.lexeme.clone(), Rc::new(function));
                     }
                 }
 
-                let class = ReefClass::new(name.lexeme.clone(), class_methods);
-                let class_val = Value::Callable(Rc::new(class) as Rc<dyn ReefCallable>);
+                let fields = fields
+                    .iter()
+                    .map(|(field, default)| (field.lexeme.clone(), Rc::clone(default)))
+                    .collect();
+                let class = ReefClass::new(
+                    name.lexeme.clone(),
+                    class_methods,
+                    class_decorators,
+                    fields,
+                    *sealed,
+                    Rc::clone(&self.environment),
+                );
+                let class_val = Value::Callable(class as Rc<dyn ReefCallable>);
 
                 self.environment
                     .borrow_mut()
@@ -448,3 +906,165 @@ impl Default for Interpreter {
         Interpreter::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Value, testing};
+
+    #[test]
+    fn pipe_inserts_first_argument() {
+        let source = "
+            fun sub(a, b) { return a - b; }
+            fun double(n) { return n * 2; }
+            var result = 10 |> sub(4) |> double;
+        ";
+        assert!(matches!(testing::eval(source, "result"), Value::Int(12)));
+    }
+
+    #[test]
+    fn placeholder_builds_partial() {
+        let source = "
+            fun sub(a, b) { return a - b; }
+            var fromTen = sub(10, _);
+            var result = fromTen(3) + (1 |> sub(_, 5));
+        ";
+        assert!(matches!(testing::eval(source, "result"), Value::Int(3)));
+    }
+
+    #[test]
+    fn ints_and_floats_stay_distinct() {
+        let interpreter = testing::run(
+            "
+            var quotient = 7 / 2;
+            var mixed = 7 / 2.0;
+            var big = 9007199254740993 + 0;
+            var same = 3 == 3.0;
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        assert!(matches!(globals.get("quotient"), Ok(Value::Int(3))));
+        assert!(matches!(globals.get("mixed"), Ok(Value::Number(n)) if n == 3.5));
+        assert!(matches!(
+            globals.get("big"),
+            Ok(Value::Int(9007199254740993))
+        ));
+        assert!(matches!(globals.get("same"), Ok(Value::Boolean(true))));
+        assert_eq!(interpreter.stringify(&Value::Number(3.0)), "3.0");
+        assert_eq!(interpreter.stringify(&Value::Int(3)), "3");
+    }
+
+    #[test]
+    fn int_division_by_zero_is_an_error() {
+        assert!(testing::run("var x = 1 / 0;").is_err());
+        assert!(testing::run("var x = 1 / 0.0;").is_ok());
+    }
+
+    #[test]
+    fn partial_checks_arity() {
+        let source = "
+            fun add(a, b) { return a + b; }
+            var inc = add(_, 1);
+            inc(1, 2);
+        ";
+        assert!(testing::run(source).is_err());
+    }
+
+    #[test]
+    fn lists_index_and_assign() {
+        let interpreter = testing::run(
+            "
+            var items = [1, \"two\", [3]];
+            items[0] = items[-1][0] + 1;
+            items.push(nil);
+            var last = items.pop();
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let items = globals.get("items").expect("defined");
+        assert_eq!(interpreter.stringify(&items), r#"[4, "two", [3]]"#);
+        assert!(matches!(globals.get("last"), Ok(Value::Nil)));
+        assert!(testing::run("var x = [1][1];").is_err());
+    }
+
+    #[test]
+    fn maps_keep_insertion_order() {
+        let interpreter = testing::run(
+            "
+            var counts = {\"b\": 1, \"a\": 2};
+            counts[\"c\"] = counts[\"a\"] + counts.get(\"z\", 1);
+            counts.remove(\"b\");
+            var keys = counts.keys();
+            var nested = {\"list\": [1, \"x\"], \"empty\": {}};
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("counts"), r#"{"a": 2, "c": 3}"#);
+        assert_eq!(render("keys"), r#"["a", "c"]"#);
+        assert_eq!(render("nested"), r#"{"list": [1, "x"], "empty": {}}"#);
+        assert!(testing::run("var x = {}[\"missing\"];").is_err());
+        assert!(testing::run("var x = {[1]: 2};").is_err());
+    }
+
+    #[test]
+    fn sets_and_maps_share_key_rules() {
+        let interpreter = testing::run(
+            "
+            var primes = #{2, 3, 5, 3, 2.0};
+            var keyed = {1: \"one\", nil: \"none\", b\"k\": true};
+            keyed[1.0] = \"uno\";
+            var checks = [3 in primes, 4 in primes, 1 in keyed, \"ee\" in \"reef\", [1] in [[1]]];
+            var empty = #{};
+            var exact = #{rational(4, 2), 2, 2.0, rational(1, 2), 0.5, rational(1, 3)};
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("primes"), "#{2, 3, 5}");
+        assert_eq!(render("keyed"), r#"{1: "uno", nil: "none", b"k": true}"#);
+        assert_eq!(render("checks"), "[true, false, true, true, true]");
+        assert_eq!(render("empty"), "#{}");
+        assert_eq!(render("exact"), "#{2, 1/2, 1/3}");
+        assert!(testing::run("var x = #{[1]};").is_err());
+        assert!(testing::run("var x = 1 in 2;").is_err());
+    }
+
+    #[test]
+    fn compares_containers_by_value_and_objects_by_identity() {
+        let interpreter = testing::run(
+            "
+            class Point { init(x) { this.x = x; } }
+            var p = Point(1);
+            var loop = [1];
+            loop.push(loop);
+            var other = [1];
+            other.push(other);
+            fun f() {}
+            var checks = [
+                [1, [2.0, \"a\"]] == [1, [2, \"a\"]],
+                {\"a\": 1, \"b\": [2]} == {\"b\": [2], \"a\": 1},
+                #{1, 2} == #{2, 1},
+                (1, (2, 3)) == (1, (2, 3)),
+                [1, 2] == (1, 2),
+                p == p,
+                p == Point(1),
+                f == f,
+                Point == Point,
+                loop == other,
+                [1] != [2]
+            ];
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let checks = globals.get("checks").expect("defined");
+        assert_eq!(
+            interpreter.stringify(&checks),
+            "[true, true, true, true, false, true, false, true, true, true, true]"
+        );
+    }
+}
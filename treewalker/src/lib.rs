@@ -1,15 +1,26 @@
 pub mod ast_printer;
+pub mod bigint;
 pub mod class;
+pub mod decorator;
 pub mod environment;
 pub mod error;
 pub mod expr;
 pub mod func;
 pub mod interpreter;
+pub mod map;
+pub mod module;
+pub mod number;
 pub mod parser;
+pub mod rational;
 pub mod reef;
+pub mod regex;
 pub mod resolver;
 pub mod scanner;
+pub mod set;
+pub mod stdlib;
 pub mod stmt;
+#[cfg(test)]
+mod testing;
 pub mod token;
 pub use expr::{ExprKind, Value};
 pub use parser::Parser;
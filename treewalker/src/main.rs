@@ -8,14 +8,31 @@ fn main() {
 
     let command = &args[1];
 
-    let mut reef = Reef::new();
     match command.as_str() {
-        "tokenize" => {
-            let filename = &args[2];
+        "tokenize" | "run" => {
+            // `--seed N` before the filename fixes the random seed
+            let (seed, rest) = match args.get(2).map(String::as_str) {
+                Some("--seed") => match args.get(3).and_then(|n| n.parse::<u64>().ok()) {
+                    Some(seed) => (Some(seed), &args[4..]),
+                    None => {
+                        eprintln!("--seed expects a non-negative integer");
+                        std::process::exit(64);
+                    }
+                },
+                _ => (None, &args[2..]),
+            };
+            let Some((filename, script_args)) = rest.split_first() else {
+                eprintln!("Usage: reef {} [--seed N] <file> [args...]", command);
+                std::process::exit(64);
+            };
+            let mut reef = Reef::with_args(script_args.to_vec());
+            if let Some(seed) = seed {
+                reef.set_seed(seed);
+            }
             reef.run_file(filename);
         }
         "repl" => {
-            let _ = reef.run_repl();
+            let _ = Reef::new().run_repl();
         }
         _ => {
             eprintln!("Unknown command: {}", command);
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+
+use crate::{Value, bigint::BigInt, number, rational::Rational};
+
+// the hashable form of a value; values that are `==` share a key, so `1` and `1.0` are one key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    Nil,
+    Boolean(bool),
+    Int(i64),
+    // bigints, whole floats too large for an i64 and rationals no float equals, by their exact text
+    Exact(String),
+    Float(u64),
+    String(String),
+    Bytes(Bytes),
+    Tuple(Vec<Key>),
+}
+
+impl Key {
+    // mutable and identity-compared values can't be keys
+    pub fn new(value: &Value) -> Result<Key, String> {
+        Ok(match value {
+            Value::Nil => Key::Nil,
+            Value::Boolean(b) => Key::Boolean(*b),
+            Value::Int(n) => Key::Int(*n),
+            Value::BigInt(n) => Key::Exact(n.to_string()),
+            Value::Rational(r) => rational_key(r),
+            Value::Number(n) if n.fract() == 0.0 => {
+                if (i64::MIN as f64..i64::MAX as f64).contains(n) {
+                    Key::Int(*n as i64)
+                } else {
+                    Key::Exact(format!("{:.0}", n))
+                }
+            }
+            Value::Number(n) => Key::Float(n.to_bits()),
+            Value::String(s) => Key::String(s.clone()),
+            Value::Bytes(buf) => Key::Bytes(buf.clone()),
+            // only tuples of hashable values are hashable
+            Value::Tuple(items) => {
+                Key::Tuple(items.iter().map(Key::new).collect::<Result<_, _>>()?)
+            }
+            other => return Err(format!("{} values can't be keys", other.type_name())),
+        })
+    }
+}
+
+// integral rationals key like the integer and exactly representable ones like the float
+fn rational_key(r: &Rational) -> Key {
+    if *r.denominator() == BigInt::one() {
+        return match r.numerator().to_i64() {
+            Some(n) => Key::Int(n),
+            None => Key::Exact(r.numerator().to_string()),
+        };
+    }
+    match exact_float(r) {
+        Some(n) => Key::Float(n.to_bits()),
+        None => Key::Exact(r.to_string()),
+    }
+}
+
+// the float whose exact value is `r`, if there is one
+fn exact_float(r: &Rational) -> Option<f64> {
+    let candidate = r.to_f64();
+    (number::float_to_rational(candidate).as_ref() == Some(r)).then_some(candidate)
+}
+
+// map that iterates in insertion order, so printing and serializing are stable
+#[derive(Debug, Clone, Default)]
+pub struct ReefMap {
+    entries: Vec<(Value, Value)>,
+    index: HashMap<Key, usize>,
+}
+
+impl ReefMap {
+    pub fn new() -> Self {
+        ReefMap::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.lookup(&Key::String(key.to_string()))
+    }
+
+    pub fn get_value(&self, key: &Value) -> Result<Option<&Value>, String> {
+        Ok(self.lookup(&Key::new(key)?))
+    }
+
+    fn lookup(&self, key: &Key) -> Option<&Value> {
+        self.index
+            .get(key)
+            .map(|position| &self.entries[*position].1)
+    }
+
+    pub fn insert(&mut self, key: String, value: Value) {
+        self.insert_value(Value::String(key), value)
+            .expect("strings are always keys");
+    }
+
+    // returns the value that was replaced, if any; the original key is kept
+    pub fn insert_value(&mut self, key: Value, value: Value) -> Result<Option<Value>, String> {
+        let hashed = Key::new(&key)?;
+        match self.index.get(&hashed) {
+            Some(position) => Ok(Some(std::mem::replace(
+                &mut self.entries[*position].1,
+                value,
+            ))),
+            None => {
+                self.index.insert(hashed, self.entries.len());
+                self.entries.push((key, value));
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn remove_value(&mut self, key: &Value) -> Result<Option<Value>, String> {
+        let Some(position) = self.index.remove(&Key::new(key)?) else {
+            return Ok(None);
+        };
+        let (_, value) = self.entries.remove(position);
+        for later in self.index.values_mut() {
+            if *later > position {
+                *later -= 1;
+            }
+        }
+        Ok(Some(value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Value, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+}
+
+impl FromIterator<(String, Value)> for ReefMap {
+    fn from_iter<T: IntoIterator<Item = (String, Value)>>(iter: T) -> Self {
+        let mut map = ReefMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
@@ -0,0 +1,43 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{Token, Value, error::ReefError, func::NativeFunction};
+
+pub type ReefModuleRef = Rc<ReefModule>;
+
+// a named namespace of natives and constants, e.g. `math.sqrt`
+#[derive(Debug)]
+pub struct ReefModule {
+    pub name: String,
+    members: RefCell<HashMap<String, Value>>,
+}
+
+impl ReefModule {
+    pub fn new(name: &str) -> Self {
+        ReefModule {
+            name: name.to_string(),
+            members: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn define(&self, name: &str, value: Value) {
+        self.members.borrow_mut().insert(name.to_string(), value);
+    }
+
+    pub fn define_native(&self, native: NativeFunction) {
+        let name = native.name.clone();
+        self.define(&name, Value::Callable(Rc::new(native)));
+    }
+
+    pub fn get(&self, name: &Token) -> Result<Value, ReefError> {
+        self.members
+            .borrow()
+            .get(&name.lexeme)
+            .cloned()
+            .ok_or_else(|| {
+                ReefError::reef_runtime_error(
+                    name,
+                    &format!("module '{}' has no member '{}'", self.name, name.lexeme),
+                )
+            })
+    }
+}
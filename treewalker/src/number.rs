@@ -0,0 +1,277 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{
+    TokenType, Value, bigint::BigInt, environment::EnvRef, error::ReefError, func::NativeFunction,
+    rational::Rational,
+};
+
+// numeric tower: int -> bigint -> rational -> float; mixed operands widen to the higher rank
+fn rank(value: &Value) -> Option<u8> {
+    match value {
+        Value::Int(_) => Some(0),
+        Value::BigInt(_) => Some(1),
+        Value::Rational(_) => Some(2),
+        Value::Number(_) => Some(3),
+        _ => None,
+    }
+}
+
+pub fn to_bigint(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Int(n) => Some(BigInt::from_i64(*n)),
+        Value::BigInt(n) => Some(n.as_ref().clone()),
+        _ => None,
+    }
+}
+
+fn to_rational(value: &Value) -> Option<Rational> {
+    match value {
+        Value::Rational(r) => Some(r.as_ref().clone()),
+        _ => to_bigint(value).map(Rational::from_integer),
+    }
+}
+
+// demotes results that fit back into an i64
+pub fn from_bigint(n: BigInt) -> Value {
+    match n.to_i64() {
+        Some(small) => Value::Int(small),
+        None => Value::BigInt(Rc::new(n)),
+    }
+}
+
+pub fn arithmetic(operator: TokenType, left: &Value, right: &Value) -> Result<Value, String> {
+    let rank = match (rank(left), rank(right)) {
+        (Some(l), Some(r)) => l.max(r),
+        _ => return Err("Binary evaluation error".to_string()),
+    };
+    if let (Value::Int(l), Value::Int(r)) = (left, right) {
+        let result = match operator {
+            TokenType::Plus => l.checked_add(*r),
+            TokenType::Minus => l.checked_sub(*r),
+            TokenType::Star => l.checked_mul(*r),
+            TokenType::Slash if *r == 0 => return Err("division by zero".to_string()),
+            TokenType::Slash => l.checked_div(*r),
+            _ => return Err("Binary evaluation error".to_string()),
+        };
+        // on overflow fall through to the bigint path
+        if let Some(n) = result {
+            return Ok(Value::Int(n));
+        }
+    }
+    match rank {
+        3 => {
+            let (l, r) = (
+                left.as_float().expect("ranked as number"),
+                right.as_float().expect("ranked as number"),
+            );
+            match operator {
+                TokenType::Plus => Ok(Value::Number(l + r)),
+                TokenType::Minus => Ok(Value::Number(l - r)),
+                TokenType::Star => Ok(Value::Number(l * r)),
+                TokenType::Slash => Ok(Value::Number(l / r)),
+                _ => Err("Binary evaluation error".to_string()),
+            }
+        }
+        2 => {
+            let (l, r) = (
+                to_rational(left).expect("ranked as rational"),
+                to_rational(right).expect("ranked as rational"),
+            );
+            let result = match operator {
+                TokenType::Plus => &l + &r,
+                TokenType::Minus => &l - &r,
+                TokenType::Star => &l * &r,
+                TokenType::Slash => l
+                    .checked_div(&r)
+                    .ok_or_else(|| "division by zero".to_string())?,
+                _ => return Err("Binary evaluation error".to_string()),
+            };
+            Ok(Value::Rational(Rc::new(result)))
+        }
+        _ => {
+            let (l, r) = (
+                to_bigint(left).expect("ranked as integer"),
+                to_bigint(right).expect("ranked as integer"),
+            );
+            let result = match operator {
+                TokenType::Plus => &l + &r,
+                TokenType::Minus => &l - &r,
+                TokenType::Star => &l * &r,
+                TokenType::Slash => {
+                    l.div_rem(&r)
+                        .ok_or_else(|| "division by zero".to_string())?
+                        .0
+                }
+                _ => return Err("Binary evaluation error".to_string()),
+            };
+            Ok(from_bigint(result))
+        }
+    }
+}
+
+// None when either side isn't a number; Some(None) for unordered floats (NaN)
+pub fn compare(left: &Value, right: &Value) -> Option<Option<Ordering>> {
+    let rank = rank(left)?.max(rank(right)?);
+    Some(match (left, right) {
+        (Value::Int(l), Value::Int(r)) => Some(l.cmp(r)),
+        (Value::Number(l), Value::Number(r)) => l.partial_cmp(r),
+        // ints up to 2^53 convert exactly, which skips the rational path for the common case
+        (Value::Number(l), Value::Int(r)) if r.unsigned_abs() <= 1 << 53 => {
+            l.partial_cmp(&(*r as f64))
+        }
+        (Value::Int(l), Value::Number(r)) if l.unsigned_abs() <= 1 << 53 => {
+            (*l as f64).partial_cmp(r)
+        }
+        (Value::Number(l), exact) => compare_exact(*l, exact),
+        (exact, Value::Number(r)) => compare_exact(*r, exact).map(Ordering::reverse),
+        _ if rank == 2 => Some(to_rational(left)?.cmp(&to_rational(right)?)),
+        _ => Some(to_bigint(left)?.cmp(&to_bigint(right)?)),
+    })
+}
+
+// a float against an exact number, by the float's exact value so equality never rounds
+fn compare_exact(float: f64, exact: &Value) -> Option<Ordering> {
+    if float.is_infinite() {
+        return Some(if float > 0.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        });
+    }
+    Some(float_to_rational(float)?.cmp(&to_rational(exact)?))
+}
+
+// the exact value of a finite float; None for NaN and the infinities
+pub fn float_to_rational(n: f64) -> Option<Rational> {
+    if !n.is_finite() {
+        return None;
+    }
+    let bits = n.to_bits();
+    let biased = ((bits >> 52) & 0x7ff) as i64;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = match biased {
+        0 => (fraction, -1074),
+        _ => (fraction | (1 << 52), biased - 1075),
+    };
+    let mantissa = BigInt::from_i64(if n < 0.0 {
+        -(mantissa as i64)
+    } else {
+        mantissa as i64
+    });
+    let mut power = BigInt::one();
+    let mut remaining = exponent.unsigned_abs();
+    while remaining > 0 {
+        let step = remaining.min(62);
+        power = &power * &BigInt::from_i64(1 << step);
+        remaining -= step;
+    }
+    if exponent >= 0 {
+        Some(Rational::from_integer(&mantissa * &power))
+    } else {
+        Rational::new(mantissa, power)
+    }
+}
+
+pub fn negate(value: &Value) -> Option<Value> {
+    match value {
+        Value::Int(n) => Some(
+            n.checked_neg()
+                .map(Value::Int)
+                .unwrap_or_else(|| from_bigint(-&BigInt::from_i64(*n))),
+        ),
+        Value::BigInt(n) => Some(from_bigint(-n.as_ref())),
+        Value::Rational(r) => Some(Value::Rational(Rc::new(-r.as_ref()))),
+        Value::Number(n) => Some(Value::Number(-n)),
+        _ => None,
+    }
+}
+
+pub fn define_natives(globals: &EnvRef) {
+    let rational = NativeFunction::new("rational", 2, |_interpreter, args| {
+        match (to_bigint(&args[0]), to_bigint(&args[1])) {
+            (Some(numerator), Some(denominator)) => Rational::new(numerator, denominator)
+                .map(|r| Value::Rational(Rc::new(r)))
+                .ok_or_else(|| {
+                    ReefError::reef_native_error("rational() denominator must be non-zero")
+                }),
+            _ => Err(ReefError::reef_native_error(
+                "rational() expects integer numerator and denominator",
+            )),
+        }
+    });
+    globals
+        .borrow_mut()
+        .define("rational".to_string(), Value::Callable(Rc::new(rational)))
+        .expect("expect rational function to be definable");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Value, testing};
+
+    #[test]
+    fn ints_promote_to_bigints_on_overflow() {
+        let interpreter = testing::run(
+            "
+            var big = 9223372036854775807 + 1;
+            var back = big - 1;
+            var product = 4294967296 * 4294967296 * 4294967296;
+            var literal = 0x1_0000_0000_0000_0000;
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("big"), "9223372036854775808");
+        assert!(matches!(globals.get("back"), Ok(Value::Int(i64::MAX))));
+        assert_eq!(render("product"), "79228162514264337593543950336");
+        assert_eq!(render("literal"), "18446744073709551616");
+    }
+
+    #[test]
+    fn rationals_are_exact() {
+        let interpreter = testing::run(
+            "
+            var third = rational(1, 3);
+            var sum = third + third + third;
+            var mixed = rational(1, 2) + 1;
+            var smaller = rational(1, 3) < rational(1, 2);
+            var equal = rational(4, 2) == 2;
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("third"), "1/3");
+        assert_eq!(render("sum"), "1");
+        assert_eq!(render("mixed"), "3/2");
+        assert_eq!(render("smaller"), "true");
+        assert_eq!(render("equal"), "true");
+        assert!(testing::run("var x = rational(1, 0);").is_err());
+        assert!(testing::run("var x = rational(1, 2) / 0;").is_err());
+    }
+
+    #[test]
+    fn floats_compare_by_exact_value() {
+        let interpreter = testing::run(
+            "
+            var checks = [
+                9007199254740993 == 9007199254740992.0,
+                9007199254740993 > 9007199254740992.0,
+                18446744073709551616 == 18446744073709551616.0,
+                rational(1, 3) == 1 / 3.0,
+                rational(1, 2) == 0.5,
+                rational(1, 2) < math.INF,
+            ];
+            var sizes = [#{9007199254740993, 9007199254740992.0}.len(), #{rational(1, 2), 0.5}.len()];
+            var lookups = [{rational(1, 2): \"half\"}[0.5], 1 / 3.0 in {rational(1, 3): \"x\"}];
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("checks"), "[false, true, true, false, true, true]");
+        assert_eq!(render("sizes"), "[2, 1]");
+        assert_eq!(render("lookups"), r#"["half", false]"#);
+    }
+}
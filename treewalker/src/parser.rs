@@ -42,6 +42,10 @@ impl Parser {
         self.tokens.get(self.current)
     }
 
+    fn peek_next(&self) -> Option<&Token> {
+        self.tokens.get(self.current + 1)
+    }
+
     fn is_at_end(&self) -> bool {
         self.current >= self.tokens.len()
     }
@@ -84,13 +88,24 @@ impl Parser {
     fn declaration(&mut self) -> Result<StmtKind, ReefError> {
         let decl_result = {
             if self.match_type(&[TokenType::Fun]) {
-                return self.function("function");
+                return self.function("function", Vec::new());
+            }
+            if self.check(&TokenType::At) {
+                let decorators = self.decorators()?;
+                self.consume(TokenType::Fun, "expect 'fun' after decorators")?;
+                return self.function("function", decorators);
             }
             if self.match_type(&[TokenType::Var]) {
                 return self.var_declaration();
             }
             if self.match_type(&[TokenType::Class]) {
-                return self.class_declaration();
+                return self.class_declaration(false);
+            }
+            // `sealed` is only special right before `class`, so it stays usable as a name
+            if self.check_sealed() {
+                self.advance();
+                self.advance();
+                return self.class_declaration(true);
             }
             self.statement()
         };
@@ -101,17 +116,70 @@ impl Parser {
         decl_result
     }
 
-    fn class_declaration(&mut self) -> Result<StmtKind, ReefError> {
+    fn check_sealed(&self) -> bool {
+        let is_sealed = self.peek().is_some_and(|token| {
+            token.token_type == TokenType::Identifier && token.lexeme == "sealed"
+        });
+        is_sealed
+            && self
+                .peek_next()
+                .is_some_and(|token| token.token_type == TokenType::Class)
+    }
+
+    fn class_declaration(&mut self, sealed: bool) -> Result<StmtKind, ReefError> {
         let name = self
             .consume(TokenType::Identifier, "expect class name")?
             .clone();
         self.consume(TokenType::LeftBrace, "expect '{' before class body")?;
         let mut methods: Vec<StmtKind> = Vec::new();
-        if !&self.check(&TokenType::RightBrace) && !self.is_at_end() {
-            methods.push(self.function("method")?);
+        let mut fields: Vec<(Token, Expr)> = Vec::new();
+        while !&self.check(&TokenType::RightBrace) && !self.is_at_eof() {
+            if self.match_type(&[TokenType::Var]) {
+                let (field, default) = self.field_declaration()?;
+                if fields
+                    .iter()
+                    .any(|(declared, _)| declared.lexeme == field.lexeme)
+                {
+                    return Err(ReefError::reef_error_at_line(
+                        &field,
+                        "field is already declared",
+                    ));
+                }
+                fields.push((field, default));
+                continue;
+            }
+            let decorators = self.decorators()?;
+            methods.push(self.function("method", decorators)?);
         }
         self.consume(TokenType::RightBrace, "Expect '}' after class body")?;
-        Ok(StmtKind::Class { name, methods })
+        Ok(StmtKind::Class {
+            name,
+            methods,
+            fields,
+            sealed,
+        })
+    }
+
+    // `var x = default;` in a class body; the default is evaluated for each new instance
+    fn field_declaration(&mut self) -> Result<(Token, Expr), ReefError> {
+        let name = self.member_name("expect field name")?;
+        let mut default = Rc::new(ExprKind::None);
+        if self.match_type(&[TokenType::Equal]) {
+            default = self.expression()?;
+        }
+        self.consume(TokenType::Semicolon, "expected ';' after field declaration")?;
+        Ok((name, default))
+    }
+
+    // class members may be `#private`; everything else only takes plain identifiers
+    fn member_name(&mut self, message: &str) -> Result<Token, ReefError> {
+        if self.match_type(&[TokenType::PrivateName]) {
+            return Ok(self
+                .previous()
+                .expect("token should exist after match")
+                .clone());
+        }
+        Ok(self.consume(TokenType::Identifier, message)?.clone())
     }
 
     fn var_declaration(&mut self) -> Result<StmtKind, ReefError> {
@@ -126,13 +194,20 @@ impl Parser {
         Ok(StmtKind::Var { name, initializer })
     }
 
-    fn function(&mut self, kind: &str) -> Result<StmtKind, ReefError> {
-        let name = &self
-            .consume(
-                TokenType::Identifier,
-                &format!("expect '(' after {} name", { kind }),
-            )?
-            .clone();
+    fn decorators(&mut self) -> Result<Vec<Expr>, ReefError> {
+        let mut decorators: Vec<Expr> = Vec::new();
+        while self.match_type(&[TokenType::At]) {
+            decorators.push(self.call()?);
+        }
+        Ok(decorators)
+    }
+
+    fn function(&mut self, kind: &str, decorators: Vec<Expr>) -> Result<StmtKind, ReefError> {
+        let message = format!("expect '(' after {} name", { kind });
+        let name = &match kind {
+            "method" => self.member_name(&message)?,
+            _ => self.consume(TokenType::Identifier, &message)?.clone(),
+        };
         self.consume(TokenType::LeftParen, "expect '(' before function params")?;
         let mut parameters: Vec<Token> = Vec::new();
         if !&self.check(&TokenType::RightParen) && !self.is_at_end() {
@@ -167,6 +242,7 @@ impl Parser {
                 name: name.clone(),
                 parameters,
                 body,
+                decorators,
             })
         }
     }
@@ -356,7 +432,7 @@ impl Parser {
     }
 
     fn assignment(&mut self) -> Result<Expr, ReefError> {
-        let expr = self.or_expression()?;
+        let expr = self.pipe()?;
         if self.match_type(&[TokenType::Equal]) {
             let equals = self
                 .previous()
@@ -378,6 +454,18 @@ impl Parser {
                         value,
                     }));
                 }
+                ExprKind::Index {
+                    object,
+                    bracket,
+                    index,
+                } => {
+                    return Ok(Rc::new(ExprKind::SetIndex {
+                        object: Rc::clone(object),
+                        bracket: bracket.clone(),
+                        index: Rc::clone(index),
+                        value,
+                    }));
+                }
                 _ => {
                     return Err(ReefError::reef_general_error(&format!(
                         "invalid assignment target: {:?}",
@@ -390,6 +478,49 @@ impl Parser {
         Ok(expr)
     }
 
+    fn pipe(&mut self) -> Result<Expr, ReefError> {
+        let mut expr = self.or_expression()?;
+        while self.match_type(&[TokenType::Pipe]) {
+            let operator = self
+                .previous()
+                .expect("token should exist after match")
+                .clone();
+            let target = self.or_expression()?;
+            expr = Self::pipe_into(expr, target, operator);
+        }
+        Ok(expr)
+    }
+
+    // `x |> f(a)` becomes `f(x, a)`, or fills the first `_` when the call has one
+    fn pipe_into(value: Expr, target: Expr, operator: Token) -> Expr {
+        match target.as_ref() {
+            ExprKind::Call {
+                callee,
+                token,
+                arguments,
+            } => {
+                let mut arguments = arguments.clone();
+                match arguments
+                    .iter()
+                    .position(|arg| matches!(arg.as_ref(), ExprKind::Placeholder { .. }))
+                {
+                    Some(index) => arguments[index] = value,
+                    None => arguments.insert(0, value),
+                }
+                Rc::new(ExprKind::Call {
+                    callee: Rc::clone(callee),
+                    token: token.clone(),
+                    arguments,
+                })
+            }
+            _ => Rc::new(ExprKind::Call {
+                callee: target,
+                token: operator,
+                arguments: vec![value],
+            }),
+        }
+    }
+
     fn equality(&mut self) -> Result<Expr, ReefError> {
         let mut expr = self.comparison()?;
         while self.match_type(&[TokenType::BangEqual, TokenType::EqualEqual]) {
@@ -411,6 +542,7 @@ impl Parser {
             TokenType::LessEqual,
             TokenType::Greater,
             TokenType::GreaterEqual,
+            TokenType::In,
         ]) {
             let operator = self
                 .previous()
@@ -470,7 +602,12 @@ impl Parser {
                         "can't have more than 255 arguments",
                     ));
                 }
-                let expr = self.expression()?;
+                let expr = if self.check_placeholder() {
+                    let token = self.advance().expect("should be a token here").clone();
+                    Rc::new(ExprKind::Placeholder { token })
+                } else {
+                    self.expression()?
+                };
                 arguments.push(expr);
                 if !self.match_type(&[TokenType::Comma]) {
                     break;
@@ -486,17 +623,42 @@ impl Parser {
         }))
     }
 
+    fn check_placeholder(&self) -> bool {
+        let is_underscore = self
+            .peek()
+            .is_some_and(|token| token.token_type == TokenType::Identifier && token.lexeme == "_");
+        let ends_argument = self.peek_next().is_some_and(|token| {
+            token.token_type == TokenType::Comma || token.token_type == TokenType::RightParen
+        });
+        is_underscore && ends_argument
+    }
+
     fn call(&mut self) -> Result<Expr, ReefError> {
         let mut expr = self.primary()?;
         loop {
             if self.match_type(&[TokenType::LeftParen]) {
                 expr = self.finish_call(expr)?;
-            } else if self.match_type(&[TokenType::Dot]) {
-                let name = self.consume(TokenType::Identifier, "Expect property name after '.'")?;
-                expr = Rc::new(ExprKind::Get {
+            } else if self.match_type(&[TokenType::LeftBracket]) {
+                let index = self.expression()?;
+                let bracket = self
+                    .consume(TokenType::RightBracket, "Expect ']' after index")?
+                    .clone();
+                expr = Rc::new(ExprKind::Index {
                     object: expr,
-                    name: name.clone(),
+                    bracket,
+                    index,
                 });
+            } else if self.match_type(&[TokenType::Dot]) {
+                let name = self.member_name("Expect property name after '.'")?;
+                if name.token_type == TokenType::PrivateName
+                    && !matches!(expr.as_ref(), ExprKind::This { .. })
+                {
+                    return Err(ReefError::reef_error_at_line(
+                        &name,
+                        "private members can only be used through 'this'",
+                    ));
+                }
+                expr = Rc::new(ExprKind::Get { object: expr, name });
             } else {
                 break;
             }
@@ -572,7 +734,7 @@ impl Parser {
                 value: Literal::Nil,
             }));
         }
-        if self.match_type(&[TokenType::Number, TokenType::String]) {
+        if self.match_type(&[TokenType::Number, TokenType::String, TokenType::Bytes]) {
             let token = self.previous().expect("should be tokens here").clone();
             let literal_value = token.literal.expect("should be literal here");
             return Ok(Rc::new(ExprKind::Literal {
@@ -586,13 +748,67 @@ impl Parser {
         }
 
         if self.match_type(&[TokenType::LeftParen]) {
+            if self.match_type(&[TokenType::RightParen]) {
+                return Ok(Rc::new(ExprKind::Tuple {
+                    elements: Vec::new(),
+                }));
+            }
             let expr = self.expression()?;
+            // a comma turns the grouping into a tuple, so `(x,)` has one element
+            if self.match_type(&[TokenType::Comma]) {
+                let mut elements = vec![expr];
+                while !self.check(&TokenType::RightParen) {
+                    elements.push(self.expression()?);
+                    if !self.match_type(&[TokenType::Comma]) {
+                        break;
+                    }
+                }
+                self.consume(TokenType::RightParen, "Expect ')' after tuple elements")?;
+                return Ok(Rc::new(ExprKind::Tuple { elements }));
+            }
             self.consume(
                 TokenType::RightParen,
                 "there should be a ')' following a '('",
             )?;
             return Ok(Rc::new(ExprKind::Grouping { expression: expr }));
         }
+        if self.match_type(&[TokenType::LeftBracket]) {
+            let mut elements: Vec<Expr> = Vec::new();
+            while !self.check(&TokenType::RightBracket) {
+                elements.push(self.expression()?);
+                if !self.match_type(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::RightBracket, "Expect ']' after list elements")?;
+            return Ok(Rc::new(ExprKind::List { elements }));
+        }
+        if self.match_type(&[TokenType::LeftBrace]) {
+            let brace = self.previous().expect("should be tokens here").clone();
+            let mut entries: Vec<(Expr, Expr)> = Vec::new();
+            while !self.check(&TokenType::RightBrace) {
+                let key = self.expression()?;
+                self.consume(TokenType::Colon, "Expect ':' after map key")?;
+                entries.push((key, self.expression()?));
+                if !self.match_type(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::RightBrace, "Expect '}' after map entries")?;
+            return Ok(Rc::new(ExprKind::Map { brace, entries }));
+        }
+        if self.match_type(&[TokenType::HashBrace]) {
+            let brace = self.previous().expect("should be tokens here").clone();
+            let mut elements: Vec<Expr> = Vec::new();
+            while !self.check(&TokenType::RightBrace) {
+                elements.push(self.expression()?);
+                if !self.match_type(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::RightBrace, "Expect '}' after set elements")?;
+            return Ok(Rc::new(ExprKind::SetLiteral { brace, elements }));
+        }
         if self.match_type(&[TokenType::This]) {
             let keyword = self.previous().expect("should be tokens here too").clone();
             return Ok(Rc::new(ExprKind::This { keyword }));
@@ -1,16 +1,17 @@
-use crate::ast_printer::AstPrinter;
 use crate::error::ReefError;
-use crate::interpreter::{self, Interpreter};
+use crate::interpreter::Interpreter;
 use crate::parser::Parser;
 use crate::resolver::Resolver;
 use crate::scanner::Scanner;
-use crate::stmt::StmtKind;
+use crate::stdlib::random::Rng;
 use std::fs;
 use std::io::{self, Write};
 
 pub struct Reef {
     had_error: bool,
     had_runtime_error: bool,
+    args: Vec<String>,
+    seed: Option<u64>,
 }
 /*
   Extended Backus-Naur Form (ebnf)
@@ -18,8 +19,12 @@ pub struct Reef {
   program       -> declaration* EOF ;
 
   declaration   -> class_decl | fun_decl | var_decl | statement ;
-  class_decl    -> "class" IDENTIFIER "{" function* "}" ;
-  fun_decl      -> "fun" function ;
+  class_decl    -> "sealed"? "class" IDENTIFIER "{" ( field_decl | decorator* method )* "}" ;
+  field_decl    -> "var" member ( "=" expression )? ";" ;
+  method        -> member "(" parameters? ")" block;
+  member        -> IDENTIFIER | "#" IDENTIFIER ; // `#private` members only through `this.`
+  fun_decl      -> decorator* "fun" function ;
+  decorator     -> "@" call ;
   function      -> IDENTIFIER "(" parameters? ")" block;
   parameters    -> IDENTIFIER ("," IDENTIFIER)* ;
   var_decl      -> "var" IDENTIFIER ("=" expression)? ";" ;
@@ -49,8 +54,11 @@ pub struct Reef {
 
   expression    -> assignment;
 
-  assignment    -> ( call "." )? IDENTIFIER "=" assignment
-                | logic_or ;
+  assignment    -> ( call "." member | IDENTIFIER ) "=" assignment
+                | call "[" expression "]" "=" assignment
+                | pipe ;
+
+  pipe          -> logic_or ( "|>" logic_or )* ;
 
   logic_or      -> logic_and ( "or" logic_and )* ;
 
@@ -58,7 +66,7 @@ pub struct Reef {
 
   equality      -> comparison ( ( "!=" | "==") comparison )* ; // a == b == c ...
 
-  comparison    -> term ( (">" | ">=" | "<" | "<=") term )*;
+  comparison    -> term ( (">" | ">=" | "<" | "<=" | "in") term )*;
 
   term          -> factor ( ("-" | "+" ) factor)* ;
 
@@ -66,30 +74,54 @@ pub struct Reef {
 
   unary         -> ("!" | "-") unary | call ;
 
-  call          -> primary ( "(" arguments ")" | "." IDENTIFIER )* ;
+  call          -> primary ( "(" arguments ")" | "." member | "[" expression "]" )* ;
+
+  arguments     -> argument ("," argument )* ;
 
-  arguments     -> expression ("," expression )* ;
+  argument      -> "_" | expression ;
 
-  primary       -> NUMBER | STRING | "true" | "false" | "nil" | "(" expression ")" | IDENTIFIER ;
+  primary       -> NUMBER | STRING | "true" | "false" | "nil" | "(" expression ")" | IDENTIFIER
+                | "(" ")" | "(" expression "," ( expression ( "," expression )* ","? )? ")"
+                | "[" ( expression ( "," expression )* ","? )? "]"
+                | "{" ( expression ":" expression ( "," expression ":" expression )* ","? )? "}"
+                | "#{" ( expression ( "," expression )* ","? )? "}" ;
 
 
 */
 
 impl Reef {
     pub fn new() -> Self {
+        Reef::with_args(Vec::new())
+    }
+
+    // `args` are the command line arguments after the script path
+    pub fn with_args(args: Vec<String>) -> Self {
         Reef {
             had_error: false,
             had_runtime_error: false,
+            args,
+            seed: None,
         }
     }
 
+    // fixes the `random` seed so runs are reproducible
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
     pub fn run(&mut self, text: &str) -> Result<(), ReefError> {
         let mut scanner = Scanner::new(text.to_string());
 
         let tokens = scanner.scan_tokens();
+        if let Some(e) = scanner.errors.first() {
+            return Err(e.clone());
+        }
         let mut parser = Parser::new(tokens);
 
-        let interpreter = Interpreter::default();
+        let mut interpreter = Interpreter::with_args(self.args.clone());
+        if let Some(seed) = self.seed {
+            interpreter.rng = Rng::new(seed);
+        }
 
         scanner.print_info();
 
@@ -109,7 +141,8 @@ impl Reef {
         if !file_contents.is_empty() {
             match self.run(&file_contents) {
                 Ok(()) => {}
-                Err(e) => println!("failed to run reef file: {:?}", e),
+                Err(ReefError::Exit(code)) => std::process::exit(code),
+                Err(e) => self.report_error(&e),
             };
         } else {
             println!("EOF  null");
@@ -131,10 +164,9 @@ impl Reef {
             if input_text.trim() == "exit" {
                 break;
             }
-            match self.run(&input_text) {
-                Ok(res) => res,
-                Err(_) => (),
-            };
+            if let Err(ReefError::Exit(code)) = self.run(&input_text) {
+                std::process::exit(code);
+            }
             self.had_runtime_error = false;
             self.had_error = false;
         }
@@ -0,0 +1,687 @@
+use std::fmt;
+
+// regular expressions compiled to a small instruction set and run on a pike vm, which tracks
+// every candidate thread in lockstep so matching is linear in the input with no backtracking
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl ClassItem {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ClassItem::Range(low, high) => (*low..=*high).contains(&c),
+            ClassItem::Digit(negated) => c.is_ascii_digit() != *negated,
+            ClassItem::Word(negated) => is_word(c) != *negated,
+            ClassItem::Space(negated) => c.is_whitespace() != *negated,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Char(char),
+    Any,
+    Class {
+        negated: bool,
+        items: Vec<ClassItem>,
+    },
+}
+
+impl Matcher {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Matcher::Char(expected) => c == *expected,
+            Matcher::Any => c != '\n',
+            Matcher::Class { negated, items } => {
+                items.iter().any(|item| item.matches(c)) != *negated
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Assertion {
+    Start,
+    End,
+    WordBoundary,
+    NotWordBoundary,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Match(Matcher),
+    Assert(Assertion),
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Match(Matcher),
+    Assert(Assertion),
+    // try the first target before the second
+    Split(usize, usize),
+    Jump(usize),
+    Save(usize),
+    Done,
+}
+
+// counted repetition is expanded inline, so bound it to keep programs small
+const MAX_REPEAT: u32 = 1000;
+// nested counts multiply, so the compiled program as a whole is bounded too
+const MAX_PROGRAM: usize = 100_000;
+// groups are parsed (and compiled) recursively, so their nesting is bounded to protect the stack
+const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct Regex {
+    source: String,
+    program: Vec<Inst>,
+    group_count: usize,
+    names: Vec<(String, usize)>,
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Regex, String> {
+        let mut parser = RegexParser {
+            chars: pattern.chars().collect(),
+            current: 0,
+            group_count: 0,
+            names: Vec::new(),
+            depth: 0,
+        };
+        let node = parser.alternation()?;
+        if parser.current < parser.chars.len() {
+            return Err(format!("unmatched ')' at offset {}", parser.current));
+        }
+        let mut program = vec![Inst::Save(0)];
+        compile(&node, &mut program)?;
+        program.push(Inst::Save(1));
+        program.push(Inst::Done);
+        Ok(Regex {
+            source: pattern.to_string(),
+            program,
+            group_count: parser.group_count,
+            names: parser.names,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    // number of capture groups, not counting the whole match
+    pub fn group_count(&self) -> usize {
+        self.group_count
+    }
+
+    pub fn names(&self) -> &[(String, usize)] {
+        &self.names
+    }
+
+    // finds the leftmost match starting at or after `start` (exactly at `start` when anchored);
+    // returns char offsets as (start, end) pairs, index 0 being the whole match
+    pub fn captures_at(
+        &self,
+        text: &[char],
+        start: usize,
+        anchored: bool,
+    ) -> Option<Vec<Option<(usize, usize)>>> {
+        let slot_count = (self.group_count + 1) * 2;
+        let mut current: Vec<Thread> = Vec::new();
+        let mut visited = vec![false; self.program.len()];
+        let mut matched: Option<Vec<Option<usize>>> = None;
+        for pos in start..=text.len() {
+            if matched.is_none() && (!anchored || pos == start) {
+                self.add_thread(
+                    &mut current,
+                    &mut visited,
+                    0,
+                    vec![None; slot_count],
+                    text,
+                    pos,
+                );
+            }
+            // unanchored searches keep seeding threads until something matches
+            if current.is_empty() && (matched.is_some() || anchored) {
+                break;
+            }
+            let mut next: Vec<Thread> = Vec::new();
+            let mut next_visited = vec![false; self.program.len()];
+            for thread in current {
+                match &self.program[thread.pc] {
+                    Inst::Match(matcher) => {
+                        if pos < text.len() && matcher.matches(text[pos]) {
+                            self.add_thread(
+                                &mut next,
+                                &mut next_visited,
+                                thread.pc + 1,
+                                thread.slots,
+                                text,
+                                pos + 1,
+                            );
+                        }
+                    }
+                    Inst::Done => {
+                        // lower priority threads can't win any more
+                        matched = Some(thread.slots);
+                        break;
+                    }
+                    _ => unreachable!("only consuming instructions are queued"),
+                }
+            }
+            current = next;
+            visited = next_visited;
+        }
+        let slots = matched?;
+        Some(
+            slots
+                .chunks(2)
+                .map(|pair| match (pair[0], pair[1]) {
+                    (Some(start), Some(end)) => Some((start, end)),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
+    // follows the non-consuming instructions from `pc`, queueing threads in priority order;
+    // an explicit stack keeps long chains of splits from exhausting the call stack
+    fn add_thread(
+        &self,
+        threads: &mut Vec<Thread>,
+        visited: &mut [bool],
+        pc: usize,
+        slots: Vec<Option<usize>>,
+        text: &[char],
+        pos: usize,
+    ) {
+        let mut pending = vec![(pc, slots)];
+        while let Some((pc, mut slots)) = pending.pop() {
+            if visited[pc] {
+                continue;
+            }
+            visited[pc] = true;
+            match &self.program[pc] {
+                Inst::Jump(target) => pending.push((*target, slots)),
+                Inst::Split(first, second) => {
+                    // the second target goes underneath so the first is followed first
+                    pending.push((*second, slots.clone()));
+                    pending.push((*first, slots));
+                }
+                Inst::Save(slot) => {
+                    slots[*slot] = Some(pos);
+                    pending.push((pc + 1, slots));
+                }
+                Inst::Assert(assertion) => {
+                    if holds(*assertion, text, pos) {
+                        pending.push((pc + 1, slots));
+                    }
+                }
+                Inst::Match(_) | Inst::Done => threads.push(Thread { pc, slots }),
+            }
+        }
+    }
+}
+
+impl fmt::Display for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/", self.source)
+    }
+}
+
+struct Thread {
+    pc: usize,
+    slots: Vec<Option<usize>>,
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn holds(assertion: Assertion, text: &[char], pos: usize) -> bool {
+    let at_boundary = || {
+        let before = pos > 0 && is_word(text[pos - 1]);
+        let after = pos < text.len() && is_word(text[pos]);
+        before != after
+    };
+    match assertion {
+        Assertion::Start => pos == 0,
+        Assertion::End => pos == text.len(),
+        Assertion::WordBoundary => at_boundary(),
+        Assertion::NotWordBoundary => !at_boundary(),
+    }
+}
+
+fn compile(node: &Node, program: &mut Vec<Inst>) -> Result<(), String> {
+    if program.len() > MAX_PROGRAM {
+        return Err("pattern too large".to_string());
+    }
+    match node {
+        Node::Empty => {}
+        Node::Match(matcher) => program.push(Inst::Match(matcher.clone())),
+        Node::Assert(assertion) => program.push(Inst::Assert(*assertion)),
+        Node::Group(inner, index) => match index {
+            Some(index) => {
+                program.push(Inst::Save(index * 2));
+                compile(inner, program)?;
+                program.push(Inst::Save(index * 2 + 1));
+            }
+            None => compile(inner, program)?,
+        },
+        Node::Concat(nodes) => {
+            for node in nodes {
+                compile(node, program)?;
+            }
+        }
+        Node::Alternate(branches) => {
+            // split to each branch in turn; every branch but the last jumps to the end
+            let mut jumps = Vec::new();
+            for (i, branch) in branches.iter().enumerate() {
+                if i + 1 < branches.len() {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(branch, program)?;
+                    jumps.push(program.len());
+                    program.push(Inst::Jump(0));
+                    let next = program.len();
+                    program[split] = Inst::Split(split + 1, next);
+                } else {
+                    compile(branch, program)?;
+                }
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat {
+            node,
+            min,
+            max,
+            greedy,
+        } => {
+            for _ in 0..*min {
+                compile(node, program)?;
+            }
+            let split = |body: usize, out: usize| {
+                if *greedy {
+                    Inst::Split(body, out)
+                } else {
+                    Inst::Split(out, body)
+                }
+            };
+            match max {
+                None => {
+                    let start = program.len();
+                    program.push(Inst::Jump(0));
+                    compile(node, program)?;
+                    program.push(Inst::Jump(start));
+                    let out = program.len();
+                    program[start] = split(start + 1, out);
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Jump(0));
+                        compile(node, program)?;
+                    }
+                    let out = program.len();
+                    for start in splits {
+                        program[start] = split(start + 1, out);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+struct RegexParser {
+    chars: Vec<char>,
+    current: usize,
+    group_count: usize,
+    names: Vec<(String, usize)>,
+    // how many groups enclose the current position
+    depth: usize,
+}
+
+impl RegexParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.current).copied()
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at offset {}", message, self.current)
+    }
+
+    fn alternation(&mut self) -> Result<Node, String> {
+        let mut branches = vec![self.concat()?];
+        while self.peek() == Some('|') {
+            self.current += 1;
+            branches.push(self.concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().expect("one branch")
+        } else {
+            Node::Alternate(branches)
+        })
+    }
+
+    fn concat(&mut self) -> Result<Node, String> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().expect("one node"),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn quantified(&mut self, atom: Node) -> Result<Node, String> {
+        let (min, max) = match self.peek() {
+            Some('{') => match self.counted()? {
+                Some(bounds) => bounds,
+                None => return Ok(atom),
+            },
+            Some(c @ ('*' | '+' | '?')) => {
+                self.current += 1;
+                match c {
+                    '*' => (0, None),
+                    '+' => (1, None),
+                    _ => (0, Some(1)),
+                }
+            }
+            _ => return Ok(atom),
+        };
+        if matches!(atom, Node::Assert(_)) {
+            return Err(self.error("nothing to repeat"));
+        }
+        let greedy = if self.peek() == Some('?') {
+            self.current += 1;
+            false
+        } else {
+            true
+        };
+        if matches!(self.peek(), Some('*' | '+' | '?')) {
+            return Err(self.error("nothing to repeat"));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    // `{n}`, `{n,}` or `{n,m}`; anything else leaves the brace to be read as a literal
+    fn counted(&mut self) -> Result<Option<(u32, Option<u32>)>, String> {
+        let rest: String = self.chars[self.current..].iter().collect();
+        let Some(end) = rest.find('}') else {
+            return Ok(None);
+        };
+        let body = &rest[1..end];
+        let number = |text: &str| text.parse::<u32>().ok();
+        let bounds = match body.split_once(',') {
+            None => number(body).map(|n| (n, Some(n))),
+            Some((min, "")) => number(min).map(|n| (n, None)),
+            Some((min, max)) => number(min).zip(number(max)).map(|(n, m)| (n, Some(m))),
+        };
+        let Some((min, max)) = bounds else {
+            return Ok(None);
+        };
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error("repetition bounds out of order"));
+        }
+        if max.unwrap_or(min) > MAX_REPEAT {
+            return Err(self.error("repetition count too large"));
+        }
+        self.current += body.chars().count() + 2;
+        Ok(Some((min, max)))
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        let c = self.peek().expect("caller checked for input");
+        self.current += 1;
+        match c {
+            '(' => self.group(),
+            '[' => self.class(),
+            '.' => Ok(Node::Match(Matcher::Any)),
+            '^' => Ok(Node::Assert(Assertion::Start)),
+            '$' => Ok(Node::Assert(Assertion::End)),
+            '\\' => self.escape(false).map(|item| match item {
+                Escape::Char(c) => Node::Match(Matcher::Char(c)),
+                Escape::Class(item) => Node::Match(Matcher::Class {
+                    negated: false,
+                    items: vec![item],
+                }),
+                Escape::Assert(assertion) => Node::Assert(assertion),
+            }),
+            '*' | '+' | '?' => {
+                self.current -= 1;
+                Err(self.error("nothing to repeat"))
+            }
+            c => Ok(Node::Match(Matcher::Char(c))),
+        }
+    }
+
+    fn group(&mut self) -> Result<Node, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("groups nested too deeply"));
+        }
+        let rest: String = self.chars[self.current..].iter().take(3).collect();
+        let index = if rest.starts_with("?:") {
+            self.current += 2;
+            None
+        } else if rest.starts_with("?P<") || rest.starts_with("?<") {
+            self.current += if rest.starts_with("?P<") { 3 } else { 2 };
+            let mut name = String::new();
+            loop {
+                match self.peek() {
+                    Some('>') => break,
+                    Some(c) if is_word(c) => name.push(c),
+                    _ => return Err(self.error("invalid group name")),
+                }
+                self.current += 1;
+            }
+            self.current += 1;
+            if name.is_empty() || self.names.iter().any(|(existing, _)| *existing == name) {
+                return Err(self.error("invalid group name"));
+            }
+            self.group_count += 1;
+            self.names.push((name, self.group_count));
+            Some(self.group_count)
+        } else if rest.starts_with('?') {
+            return Err(self.error("unsupported group syntax"));
+        } else {
+            self.group_count += 1;
+            Some(self.group_count)
+        };
+        self.depth += 1;
+        let inner = self.alternation()?;
+        self.depth -= 1;
+        if self.peek() != Some(')') {
+            return Err(self.error("missing ')'"));
+        }
+        self.current += 1;
+        Ok(Node::Group(Box::new(inner), index))
+    }
+
+    fn class(&mut self) -> Result<Node, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.current += 1;
+        }
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("missing ']'"))?;
+            self.current += 1;
+            let low = match c {
+                ']' if !first => break,
+                '\\' => match self.escape(true)? {
+                    Escape::Char(c) => c,
+                    Escape::Class(item) => {
+                        items.push(item);
+                        first = false;
+                        continue;
+                    }
+                    Escape::Assert(_) => unreachable!("assertions aren't escapes inside classes"),
+                },
+                c => c,
+            };
+            first = false;
+            let is_range = self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.current + 1)
+                    .is_some_and(|next| *next != ']');
+            if !is_range {
+                items.push(ClassItem::Range(low, low));
+                continue;
+            }
+            self.current += 1;
+            let high = match self.peek() {
+                Some('\\') => {
+                    self.current += 1;
+                    match self.escape(true)? {
+                        Escape::Char(c) => c,
+                        _ => return Err(self.error("invalid range in class")),
+                    }
+                }
+                Some(c) => {
+                    self.current += 1;
+                    c
+                }
+                None => return Err(self.error("missing ']'")),
+            };
+            if high < low {
+                return Err(self.error("range out of order in class"));
+            }
+            items.push(ClassItem::Range(low, high));
+        }
+        Ok(Node::Match(Matcher::Class { negated, items }))
+    }
+
+    // called after a backslash
+    fn escape(&mut self, in_class: bool) -> Result<Escape, String> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("trailing backslash"))?;
+        self.current += 1;
+        Ok(match c {
+            'd' => Escape::Class(ClassItem::Digit(false)),
+            'D' => Escape::Class(ClassItem::Digit(true)),
+            'w' => Escape::Class(ClassItem::Word(false)),
+            'W' => Escape::Class(ClassItem::Word(true)),
+            's' => Escape::Class(ClassItem::Space(false)),
+            'S' => Escape::Class(ClassItem::Space(true)),
+            'b' if !in_class => Escape::Assert(Assertion::WordBoundary),
+            'B' if !in_class => Escape::Assert(Assertion::NotWordBoundary),
+            'n' => Escape::Char('\n'),
+            'r' => Escape::Char('\r'),
+            't' => Escape::Char('\t'),
+            c if c.is_ascii_alphanumeric() => {
+                self.current -= 1;
+                return Err(self.error(&format!("unknown escape '\\{}'", c)));
+            }
+            c => Escape::Char(c),
+        })
+    }
+}
+
+enum Escape {
+    Char(char),
+    Class(ClassItem),
+    Assert(Assertion),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Regex;
+
+    // the whole match and each group as strings
+    fn captures(pattern: &str, text: &str) -> Option<Vec<Option<String>>> {
+        let regex = Regex::new(pattern).expect("valid pattern");
+        let chars: Vec<char> = text.chars().collect();
+        regex.captures_at(&chars, 0, false).map(|groups| {
+            groups
+                .into_iter()
+                .map(|group| group.map(|(start, end)| chars[start..end].iter().collect()))
+                .collect()
+        })
+    }
+
+    fn whole(pattern: &str, text: &str) -> Option<String> {
+        captures(pattern, text).and_then(|groups| groups[0].clone())
+    }
+
+    #[test]
+    fn matches_leftmost_first() {
+        assert_eq!(whole("a+b", "xxaaab"), Some("aaab".to_string()));
+        assert_eq!(whole("a+?", "aaa"), Some("a".to_string()));
+        assert_eq!(whole("cat|category", "category"), Some("cat".to_string()));
+        assert_eq!(whole(r"\d{2,3}", "a12345"), Some("123".to_string()));
+        assert_eq!(whole(r"[^\s,]+$", "a, b, héllo"), Some("héllo".to_string()));
+        assert_eq!(whole(r"\bis\b", "this is"), Some("is".to_string()));
+        assert_eq!(whole("^b", "ab"), None);
+        assert_eq!(whole("a{,2}", "a{,2}"), Some("a{,2}".to_string()));
+        assert_eq!(
+            captures(r"(?P<key>\w+)=(\w+)?(x)?", "k=v"),
+            Some(vec![
+                Some("k=v".to_string()),
+                Some("k".to_string()),
+                Some("v".to_string()),
+                None
+            ])
+        );
+    }
+
+    #[test]
+    fn pathological_patterns_stay_linear() {
+        let text = "a".repeat(5000);
+        assert_eq!(whole("(a*)*b", &text), None);
+        assert_eq!(whole("(a|aa)+$", &text).map(|m| m.len()), Some(5000));
+    }
+
+    #[test]
+    fn rejects_malformed_patterns() {
+        for pattern in [
+            "(", "a)", "[a", "*a", "a**", r"\q", "[z-a]", "a{3,1}", "(?x)",
+        ] {
+            assert!(
+                Regex::new(pattern).is_err(),
+                "{} should not compile",
+                pattern
+            );
+        }
+        assert_eq!(
+            Regex::new("(((a?){1000}){1000}){100}").err(),
+            Some("pattern too large".to_string())
+        );
+        let nested = format!("{}{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(Regex::new(&nested).is_err());
+        let shallow = format!("{}a{}", "(".repeat(100), ")".repeat(100));
+        assert!(Regex::new(&shallow).is_ok());
+    }
+}
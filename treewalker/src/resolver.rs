@@ -51,7 +51,11 @@ impl Resolver {
                 name,
                 parameters,
                 body,
+                decorators,
             } => {
+                for decorator in decorators {
+                    self.resolve_expr(decorator)?;
+                }
                 self.declare(name)?;
                 self.define(name);
                 self.resolve_fn(parameters, body, FunctionKind::Function)?;
@@ -83,16 +87,37 @@ impl Resolver {
                 Ok(())
             }
             StmtKind::Return { keyword: _, value } => self.resolve_return(value),
-            StmtKind::Class { name, methods } => self.resolve_class(name, methods),
+            StmtKind::Class {
+                name,
+                methods,
+                fields,
+                ..
+            } => self.resolve_class(name, methods, fields),
             _ => todo!("finish statement resolutions"),
         }
     }
 
-    fn resolve_class(&mut self, name: &Token, methods: &[StmtKind]) -> Result<(), ReefError> {
+    fn resolve_class(
+        &mut self,
+        name: &Token,
+        methods: &[StmtKind],
+        fields: &[(Token, Expr)],
+    ) -> Result<(), ReefError> {
         let enclosing_class = self.current_class.clone();
-        self.current_class = ClassKind::Class;
         self.declare(name)?;
         self.define(name);
+        // defaults run where the class is declared, before there's a `this`
+        for (_, default) in fields {
+            self.resolve_expr(default)?;
+        }
+        self.current_class = ClassKind::Class;
+        for method in methods {
+            if let StmtKind::Function { decorators, .. } = method {
+                for decorator in decorators {
+                    self.resolve_expr(decorator)?;
+                }
+            }
+        }
         self.begin_scope();
         self.scopes
             .last_mut()
@@ -102,12 +127,13 @@ impl Resolver {
         for method in methods {
             match method {
                 StmtKind::Function {
-                    name: _,
+                    name: method_name,
                     parameters,
                     body,
+                    ..
                 } => {
                     let mut declaration = FunctionKind::Method;
-                    if name.lexeme == "init" {
+                    if method_name.lexeme == "init" {
                         declaration = FunctionKind::Initializer;
                     }
                     self.resolve_fn(parameters, body, declaration)?;
@@ -170,6 +196,37 @@ impl Resolver {
                 self.resolve_expr(expression)?;
                 Ok(())
             }
+            ExprKind::List { elements }
+            | ExprKind::Tuple { elements }
+            | ExprKind::SetLiteral { elements, .. } => {
+                for element in elements {
+                    self.resolve_expr(element)?;
+                }
+                Ok(())
+            }
+            ExprKind::Map { entries, .. } => {
+                for (key, value) in entries {
+                    self.resolve_expr(key)?;
+                    self.resolve_expr(value)?;
+                }
+                Ok(())
+            }
+            ExprKind::Index { object, index, .. } => {
+                self.resolve_expr(object)?;
+                self.resolve_expr(index)?;
+                Ok(())
+            }
+            ExprKind::SetIndex {
+                object,
+                index,
+                value,
+                ..
+            } => {
+                self.resolve_expr(value)?;
+                self.resolve_expr(object)?;
+                self.resolve_expr(index)?;
+                Ok(())
+            }
             ExprKind::Logical {
                 left,
                 operator: _,
@@ -180,6 +237,7 @@ impl Resolver {
                 Ok(())
             }
             ExprKind::Literal { value: _ } => Ok(()),
+            ExprKind::Placeholder { token: _ } => Ok(()),
             ExprKind::Unary {
                 operator: _operator,
                 right,
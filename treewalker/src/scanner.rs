@@ -1,17 +1,22 @@
 use std::collections::HashMap;
 
+use bytes::Bytes;
+
 use crate::{
+    bigint::BigInt,
     error::ReefError,
     token::{Literal, Token, TokenType},
 };
 
 pub struct Scanner {
-    source: String,
+    // indexed by char so non-ASCII source never splits a code point
+    source: Vec<char>,
     tokens: Vec<Token>,
     line: usize,
     start: usize,
     current: usize,
     keywords: HashMap<&'static str, TokenType>,
+    pub errors: Vec<ReefError>,
 }
 
 impl Scanner {
@@ -24,6 +29,7 @@ impl Scanner {
         keywords.insert("for", TokenType::For);
         keywords.insert("fun", TokenType::Fun);
         keywords.insert("if", TokenType::If);
+        keywords.insert("in", TokenType::In);
         keywords.insert("nil", TokenType::Nil);
         keywords.insert("or", TokenType::Or);
         keywords.insert("print", TokenType::Print);
@@ -35,29 +41,38 @@ impl Scanner {
         keywords.insert("while", TokenType::While);
 
         Scanner {
-            source,
+            source: source.chars().collect(),
             tokens: Vec::new(),
             line: 1,
             start: 0,
             current: 0,
             keywords,
+            errors: Vec::new(),
         }
     }
 
+    fn text(&self, start: usize, end: usize) -> String {
+        self.source[start..end].iter().collect()
+    }
+
+    fn error(&mut self, message: &str) {
+        self.errors.push(ReefError::reef_error(self.line, message));
+    }
+
     fn add_token(&mut self, token_type: TokenType) {
-        let lexeme = self.source[self.start..self.current].to_string();
+        let lexeme = self.text(self.start, self.current);
         self.tokens
             .push(Token::new(token_type, lexeme, None, self.line));
     }
 
     fn add_token_with_literal(&mut self, token_type: TokenType, literal: Literal) {
-        let lexeme = self.source[self.start..self.current].to_string();
+        let lexeme = self.text(self.start, self.current);
         let token = Token::new(token_type, lexeme, Some(literal), self.line);
         self.tokens.push(token);
     }
 
     fn advance(&mut self) -> char {
-        let c = self.source.chars().nth(self.current).unwrap();
+        let c = self.source[self.current];
         self.current += 1;
         c
     }
@@ -70,10 +85,30 @@ impl Scanner {
             ')' => self.add_token(TokenType::RightParen),
             '{' => self.add_token(TokenType::LeftBrace),
             '}' => self.add_token(TokenType::RightBrace),
+            '[' => self.add_token(TokenType::LeftBracket),
+            ']' => self.add_token(TokenType::RightBracket),
             ',' => self.add_token(TokenType::Comma),
+            ':' => self.add_token(TokenType::Colon),
             '.' => self.add_token(TokenType::Dot),
             ';' => self.add_token(TokenType::Semicolon),
             '*' => self.add_token(TokenType::Star),
+            '@' => self.add_token(TokenType::At),
+            '#' => {
+                if self.match_next_char('{') {
+                    self.add_token(TokenType::HashBrace);
+                } else if self.is_alpha(&self.peek()) {
+                    self.private_name();
+                } else {
+                    self.error("unexpected character");
+                }
+            }
+            '|' => {
+                if self.match_next_char('>') {
+                    self.add_token(TokenType::Pipe);
+                } else {
+                    self.error("unexpected character");
+                }
+            }
             '!' => {
                 if self.match_next_char('=') {
                     self.add_token(TokenType::BangEqual);
@@ -118,13 +153,14 @@ impl Scanner {
                 self.line += 1;
             }
             '"' => self.string(),
+            'b' if self.peek() == '"' => self.bytes(),
             _ => {
                 if self.is_digit(c) {
                     self.number();
                 } else if self.is_alpha(c) {
                     self.identifier();
                 } else {
-                    ReefError::reef_error(self.line, "unexpected character");
+                    self.error("unexpected character");
                 }
             }
         }
@@ -150,27 +186,115 @@ impl Scanner {
         while self.is_alphanumeric(&self.peek()) {
             self.advance();
         }
-        let text = &self.source[self.start..self.current];
+        let text = self.text(self.start, self.current);
         let token_type = self
             .keywords
-            .get(text)
+            .get(text.as_str())
             .copied()
             .unwrap_or(TokenType::Identifier);
         self.add_token(token_type);
     }
-    fn number(&mut self) {
-        while self.is_digit(&self.peek()) {
+    // `#name`, kept whole in the lexeme so private members never collide with public ones
+    fn private_name(&mut self) {
+        while self.is_alphanumeric(&self.peek()) {
             self.advance();
         }
+        self.add_token(TokenType::PrivateName);
+    }
+    fn number(&mut self) {
+        // rescan from the first digit so prefixes and separators are checked in one place
+        self.current = self.start;
+        match self.number_literal() {
+            Ok(literal) => self.add_token_with_literal(TokenType::Number, literal),
+            Err(message) => {
+                while self.is_alphanumeric(&self.peek()) {
+                    self.advance();
+                }
+                let lexeme = self.text(self.start, self.current);
+                self.error(&format!("{} in '{}'", message, lexeme));
+            }
+        }
+    }
+
+    fn number_literal(&mut self) -> Result<Literal, String> {
+        if self.peek() == '0' {
+            let radix = match self.peek_next() {
+                'x' | 'X' => Some((16, "hex")),
+                'o' | 'O' => Some((8, "octal")),
+                'b' | 'B' => Some((2, "binary")),
+                _ => None,
+            };
+            if let Some((radix, kind)) = radix {
+                self.advance();
+                self.advance();
+                let digits = self.digits(radix, kind)?;
+                return Ok(Self::integer_literal(&digits, radix));
+            }
+        }
+
+        let mut text = self.digits(10, "number")?;
+        let mut is_float = false;
         if self.peek() == '.' && self.is_digit(&self.peek_next()) {
+            is_float = true;
             self.advance();
-            while self.is_digit(&self.peek()) {
-                self.advance();
+            text.push('.');
+            text.push_str(&self.digits(10, "number")?);
+        }
+        if self.peek() == 'e' || self.peek() == 'E' {
+            is_float = true;
+            self.advance();
+            text.push('e');
+            if self.peek() == '+' || self.peek() == '-' {
+                text.push(self.advance());
             }
+            text.push_str(&self.digits(10, "exponent")?);
         }
-        let str_num = self.source[self.start..self.current].to_string();
-        let num_literal = Literal::Number(str_num.parse::<f64>().unwrap());
-        self.add_token_with_literal(TokenType::Number, num_literal);
+        if !is_float {
+            return Ok(Self::integer_literal(&text, 10));
+        }
+        text.parse::<f64>()
+            .map(Literal::Number)
+            .map_err(|_| "malformed number literal".to_string())
+    }
+
+    // `digits` is already validated, so only the i64 range decides the variant
+    fn integer_literal(digits: &str, radix: u32) -> Literal {
+        match i64::from_str_radix(digits, radix) {
+            Ok(n) => Literal::Int(n),
+            Err(_) => Literal::BigInt(
+                BigInt::parse_radix(digits, radix).expect("digits are valid for radix"),
+            ),
+        }
+    }
+
+    // consumes digits of `radix` with single '_' separators between them
+    fn digits(&mut self, radix: u32, kind: &str) -> Result<String, String> {
+        let mut digits = String::new();
+        let mut after_underscore = false;
+        loop {
+            let c = self.peek();
+            if c == '_' {
+                if digits.is_empty() || after_underscore {
+                    return Err("misplaced '_' separator".to_string());
+                }
+                after_underscore = true;
+            } else if c.is_digit(radix) {
+                digits.push(c);
+                after_underscore = false;
+            } else if radix != 10 && c.is_ascii_alphanumeric() {
+                return Err(format!("invalid digit '{}' in {} literal", c, kind));
+            } else {
+                break;
+            }
+            self.advance();
+        }
+        if after_underscore {
+            return Err("misplaced '_' separator".to_string());
+        }
+        if digits.is_empty() {
+            return Err(format!("expected digits in {} literal", kind));
+        }
+        Ok(digits)
     }
 
     fn string(&mut self) {
@@ -182,22 +306,80 @@ impl Scanner {
         }
 
         if self.is_at_end() {
-            ReefError::reef_error(self.line, "Unterminated string.");
+            self.error("Unterminated string.");
             return;
         }
 
         // closing "
         self.advance();
 
-        let str_val = self.source[self.start + 1..self.current - 1].to_string();
+        let str_val = self.text(self.start + 1, self.current - 1);
         self.add_token_with_literal(TokenType::String, Literal::String(str_val));
     }
 
+    // unlike strings, byte literals take escapes, since arbitrary bytes can't be typed
+    fn bytes(&mut self) {
+        // opening "
+        self.advance();
+        let mut buf = Vec::new();
+        while self.peek() != '"' && !self.is_at_end() {
+            let c = self.advance();
+            match c {
+                '\\' => match self.escape() {
+                    Some(byte) => buf.push(byte),
+                    None => {
+                        self.error("invalid escape in bytes literal");
+                    }
+                },
+                '\n' => {
+                    self.line += 1;
+                    buf.push(b'\n');
+                }
+                c => {
+                    let mut encoded = [0; 4];
+                    buf.extend_from_slice(c.encode_utf8(&mut encoded).as_bytes());
+                }
+            }
+        }
+
+        if self.is_at_end() {
+            self.error("Unterminated bytes literal.");
+            return;
+        }
+
+        // closing "
+        self.advance();
+        self.add_token_with_literal(TokenType::Bytes, Literal::Bytes(Bytes::from(buf)));
+    }
+
+    // the byte for the escape after a backslash: \xHH, \n, \r, \t, \0, \\ or \"
+    fn escape(&mut self) -> Option<u8> {
+        if self.is_at_end() {
+            return None;
+        }
+        match self.advance() {
+            'x' => {
+                let high = self.peek().to_digit(16)?;
+                self.advance();
+                let low = self.peek().to_digit(16)?;
+                self.advance();
+                Some((high * 16 + low) as u8)
+            }
+            'n' => Some(b'\n'),
+            'r' => Some(b'\r'),
+            't' => Some(b'\t'),
+            '0' => Some(0),
+            '\\' => Some(b'\\'),
+            '"' => Some(b'"'),
+            _ => None,
+        }
+    }
+
     fn match_next_char(&mut self, expected: char) -> bool {
         if self.is_at_end() {
             return false;
         }
-        let c = self.source.chars().nth(self.current).unwrap();
+        let c = self.source[self.current];
         if c != expected {
             return false;
         }
@@ -209,7 +391,7 @@ impl Scanner {
         if self.is_at_end() {
             '\0'
         } else {
-            self.source.chars().nth(self.current).unwrap()
+            self.source[self.current]
         }
     }
 
@@ -217,7 +399,7 @@ impl Scanner {
         if self.is_next_end() {
             '\0'
         } else {
-            self.source.chars().nth(self.current + 1).unwrap()
+            self.source[self.current + 1]
         }
     }
 
@@ -250,8 +432,44 @@ impl Scanner {
 
 #[cfg(test)]
 mod tests {
+    use super::Scanner;
+    use crate::{Literal, TokenType};
+
+    fn scan_number(source: &str) -> Option<Literal> {
+        let mut scanner = Scanner::new(source.to_string());
+        let tokens = scanner.scan_tokens();
+        match (&tokens[0].token_type, &tokens[0].literal) {
+            (TokenType::Number, Some(literal)) if scanner.errors.is_empty() => {
+                Some(literal.clone())
+            }
+            _ => None,
+        }
+    }
+
     #[test]
     fn test_output() {
         assert_eq!(1, 1)
     }
+
+    #[test]
+    fn number_literals() {
+        assert_eq!(scan_number("123"), Some(Literal::Int(123)));
+        assert_eq!(scan_number("1.5"), Some(Literal::Number(1.5)));
+        assert_eq!(scan_number("0xFF"), Some(Literal::Int(255)));
+        assert_eq!(scan_number("0b1010"), Some(Literal::Int(10)));
+        assert_eq!(scan_number("0o17"), Some(Literal::Int(15)));
+        assert_eq!(scan_number("1e-9"), Some(Literal::Number(1e-9)));
+        assert_eq!(scan_number("6.02E23"), Some(Literal::Number(6.02e23)));
+        assert_eq!(scan_number("1_000_000"), Some(Literal::Int(1_000_000)));
+        assert_eq!(scan_number("0xdead_beef"), Some(Literal::Int(0xdead_beef)));
+    }
+
+    #[test]
+    fn malformed_number_literals() {
+        for source in ["0x", "1__0", "1e", "1_", "0b102", "0o8", "1e+"] {
+            let mut scanner = Scanner::new(source.to_string());
+            scanner.scan_tokens();
+            assert!(!scanner.errors.is_empty(), "{} should not scan", source);
+        }
+    }
 }
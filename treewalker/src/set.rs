@@ -0,0 +1,46 @@
+use crate::{Value, map::ReefMap};
+
+// a map without values, so membership follows exactly the same key rules as map keys
+#[derive(Debug, Clone, Default)]
+pub struct ReefSet {
+    members: ReefMap,
+}
+
+impl ReefSet {
+    pub fn new() -> Self {
+        ReefSet::default()
+    }
+
+    pub fn from_values(values: impl IntoIterator<Item = Value>) -> Result<Self, String> {
+        let mut set = ReefSet::new();
+        for value in values {
+            set.insert(value)?;
+        }
+        Ok(set)
+    }
+
+    // true when the value wasn't already a member
+    pub fn insert(&mut self, value: Value) -> Result<bool, String> {
+        Ok(self.members.insert_value(value, Value::Nil)?.is_none())
+    }
+
+    pub fn contains(&self, value: &Value) -> Result<bool, String> {
+        Ok(self.members.get_value(value)?.is_some())
+    }
+
+    pub fn remove(&mut self, value: &Value) -> Result<bool, String> {
+        Ok(self.members.remove_value(value)?.is_some())
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.members.iter().map(|(member, _)| member)
+    }
+}
@@ -0,0 +1,273 @@
+use bytes::{Bytes, BytesMut};
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    module::ReefModule,
+    stdlib::{expect_at_most, int_arg, list_arg, string::slice_bounds, string_arg, type_error},
+};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+pub fn module() -> ReefModule {
+    let bytes = ReefModule::new("bytes");
+    bytes.define_native(NativeFunction::new(
+        "fromString",
+        1,
+        |_interpreter, args| {
+            let s = string_arg(&args, 0, "bytes.fromString")?;
+            Ok(Value::Bytes(Bytes::copy_from_slice(s.as_bytes())))
+        },
+    ));
+    bytes.define_native(NativeFunction::new("fromList", 1, |_interpreter, args| {
+        let items = list_arg(&args, 0, "bytes.fromList")?;
+        let buf = items
+            .borrow()
+            .iter()
+            .map(|item| match item {
+                Value::Int(n) => u8::try_from(*n).map_err(|_| {
+                    ReefError::reef_native_error(&format!(
+                        "bytes.fromList() byte {} is out of range",
+                        n
+                    ))
+                }),
+                other => Err(type_error("bytes.fromList", 0, "a list of ints", other)),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        Ok(Value::Bytes(Bytes::from(buf)))
+    }));
+    bytes.define_native(NativeFunction::new("fromHex", 1, |_interpreter, args| {
+        let text = string_arg(&args, 0, "bytes.fromHex")?;
+        hex_decode(text)
+            .map(|buf| Value::Bytes(Bytes::from(buf)))
+            .map_err(|message| {
+                ReefError::reef_native_error(&format!("bytes.fromHex() {}", message))
+            })
+    }));
+    bytes.define_native(NativeFunction::new(
+        "fromBase64",
+        1,
+        |_interpreter, args| {
+            let text = string_arg(&args, 0, "bytes.fromBase64")?;
+            base64_decode(text, false)
+                .map(|buf| Value::Bytes(Bytes::from(buf)))
+                .map_err(|message| {
+                    ReefError::reef_native_error(&format!("bytes.fromBase64() {}", message))
+                })
+        },
+    ));
+    bytes
+}
+
+// methods are bound to the receiving bytes, which arrive as `args[0]`
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "len" => NativeFunction::new("len", 0, |_interpreter, args| {
+            Ok(Value::Int(receiver(&args).len() as i64))
+        }),
+        // shares the receiver's buffer rather than copying it
+        "slice" => NativeFunction::variadic("slice", 1, |_interpreter, args| {
+            expect_at_most(&args[1..], 2, "slice")?;
+            let buf = receiver(&args);
+            let start = int_arg(&args[1..], 0, "slice")?;
+            let end = match args.get(2) {
+                Some(_) => Some(int_arg(&args[1..], 1, "slice")?),
+                None => None,
+            };
+            let (start, end) = slice_bounds(start, end, buf.len());
+            Ok(Value::Bytes(buf.slice(start..end)))
+        }),
+        "decode" => NativeFunction::new("decode", 0, |_interpreter, args| {
+            String::from_utf8(receiver(&args).to_vec())
+                .map(Value::String)
+                .map_err(|e| ReefError::reef_native_error(&format!("decode() failed: {}", e)))
+        }),
+        "hex" => NativeFunction::new("hex", 0, |_interpreter, args| {
+            Ok(Value::String(hex_encode(receiver(&args))))
+        }),
+        "base64" => NativeFunction::new("base64", 0, |_interpreter, args| {
+            Ok(Value::String(base64_encode(receiver(&args), false)))
+        }),
+        "toList" => NativeFunction::new("toList", 0, |_interpreter, args| {
+            let items = receiver(&args)
+                .iter()
+                .map(|byte| Value::Int(*byte as i64))
+                .collect();
+            Ok(Value::new_list(items))
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn receiver(args: &[Value]) -> &Bytes {
+    match &args[0] {
+        Value::Bytes(buf) => buf,
+        _ => unreachable!("bytes methods are only bound to bytes"),
+    }
+}
+
+pub fn concat(left: &[u8], right: &[u8]) -> Bytes {
+    let mut buf = BytesMut::with_capacity(left.len() + right.len());
+    buf.extend_from_slice(left);
+    buf.extend_from_slice(right);
+    buf.freeze()
+}
+
+// `b"..."` with printable ascii as is and everything else escaped, so the output reads back
+pub fn display(buf: &[u8]) -> String {
+    let mut out = String::from("b\"");
+    for byte in buf {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            b' '..=b'~' => out.push(*byte as char),
+            _ => out.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub fn hex_encode(buf: &[u8]) -> String {
+    buf.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn hex_decode(text: &str) -> Result<Vec<u8>, String> {
+    let digits = text
+        .chars()
+        .map(|c| {
+            c.to_digit(16)
+                .ok_or_else(|| format!("invalid hex digit '{}'", c))
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    if !digits.len().is_multiple_of(2) {
+        return Err("needs an even number of hex digits".to_string());
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| (pair[0] * 16 + pair[1]) as u8)
+        .collect())
+}
+
+// padded with '=' for the standard alphabet; the url-safe alphabet leaves padding off
+pub fn base64_encode(buf: &[u8], url_safe: bool) -> String {
+    let alphabet = if url_safe { BASE64_URL } else { BASE64 };
+    let mut out = String::new();
+    for chunk in buf.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if !url_safe {
+            out.push_str(&"=".repeat(3 - chunk.len()));
+        }
+    }
+    out
+}
+
+// padding is optional in either alphabet
+pub fn base64_decode(text: &str, url_safe: bool) -> Result<Vec<u8>, String> {
+    let alphabet = if url_safe { BASE64_URL } else { BASE64 };
+    let trimmed = text.trim_end_matches('=');
+    if text.len() - trimmed.len() > 2
+        || (text.len() != trimmed.len() && !text.len().is_multiple_of(4))
+    {
+        return Err("has invalid padding".to_string());
+    }
+    let sextets = trimmed
+        .chars()
+        .map(|c| {
+            alphabet
+                .iter()
+                .position(|symbol| *symbol as char == c)
+                .map(|position| position as u32)
+                .ok_or_else(|| format!("invalid base64 character '{}'", c))
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    if sextets.len() % 4 == 1 {
+        return Err("has a truncated final group".to_string());
+    }
+    let mut out = Vec::new();
+    for chunk in sextets.chunks(4) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, sextet)| n | sextet << (18 - 6 * i));
+        for i in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_decode, base64_encode};
+    use crate::testing;
+
+    #[test]
+    fn bytes_values() {
+        let interpreter = testing::run(
+            r#"
+            var raw = b"GIF\x89\n";
+            var first = raw[0];
+            var last = raw[-1];
+            var joined = raw.slice(0, 3) + b"!";
+            var text = joined.decode();
+            var hex = raw.hex();
+            var back = bytes.fromHex(hex) == raw;
+            var encoded = "héllo".encode().base64();
+            var decoded = bytes.fromBase64(encoded).decode();
+            var listed = bytes.fromList([0, 255]).toList();
+            var size = raw.len();
+        "#,
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("raw"), r#"b"GIF\x89\n""#);
+        assert_eq!(render("first"), "71");
+        assert_eq!(render("last"), "10");
+        assert_eq!(render("text"), "GIF!");
+        assert_eq!(render("hex"), "474946890a");
+        assert_eq!(render("back"), "true");
+        assert_eq!(render("encoded"), "aMOpbGxv");
+        assert_eq!(render("decoded"), "héllo");
+        assert_eq!(render("listed"), "[0, 255]");
+        assert_eq!(render("size"), "5");
+        assert!(testing::run(r#"b"\xff".decode();"#).is_err());
+        assert!(testing::run("bytes.fromList([256]);").is_err());
+        assert!(testing::run(r#"var b = b"ab"; b[0] = 1;"#).is_err());
+    }
+
+    #[test]
+    fn base64_round_trips() {
+        for (plain, standard, url) in [
+            ("", "", ""),
+            ("f", "Zg==", "Zg"),
+            ("fo", "Zm8=", "Zm8"),
+            ("foo", "Zm9v", "Zm9v"),
+            ("\u{fb}\u{ff}", "w7vDvw==", "w7vDvw"),
+        ] {
+            assert_eq!(base64_encode(plain.as_bytes(), false), standard);
+            assert_eq!(base64_encode(plain.as_bytes(), true), url);
+            assert_eq!(
+                base64_decode(standard, false).as_deref(),
+                Ok(plain.as_bytes())
+            );
+            assert_eq!(base64_decode(url, true).as_deref(), Ok(plain.as_bytes()));
+        }
+        assert!(base64_decode("Zg=", false).is_err());
+        assert!(base64_decode("Z", false).is_err());
+        assert!(base64_decode("Zm9v!", false).is_err());
+    }
+}
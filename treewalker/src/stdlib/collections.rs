@@ -0,0 +1,341 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{
+    TokenType, Value,
+    environment::EnvRef,
+    error::ReefError,
+    func::{NativeFunction, ReefCallable},
+    interpreter::Interpreter,
+    number,
+    stdlib::{call, expect_at_most, int_arg, list_arg, type_error},
+};
+
+// range() refuses to build lists longer than this, like string repeat and pad do
+const MAX_LEN: i128 = 16 * 1024 * 1024;
+
+pub fn define_natives(globals: &EnvRef) {
+    let natives = [
+        NativeFunction::new("map", 2, |interpreter, args| {
+            let items = list_arg(&args, 0, "map")?.borrow().clone();
+            let f = callable_arg(&args, 1, "map")?;
+            let mapped = items
+                .into_iter()
+                .map(|item| call(interpreter, &f, vec![item], "map"))
+                .collect::<Result<_, _>>()?;
+            Ok(Value::new_list(mapped))
+        }),
+        NativeFunction::new("filter", 2, |interpreter, args| {
+            let items = list_arg(&args, 0, "filter")?.borrow().clone();
+            let f = callable_arg(&args, 1, "filter")?;
+            let mut kept = Vec::new();
+            for item in items {
+                if call(interpreter, &f, vec![item.clone()], "filter")?.is_truthy() {
+                    kept.push(item);
+                }
+            }
+            Ok(Value::new_list(kept))
+        }),
+        // reduce(list, f, [initial]); without an initial value the first element seeds it
+        NativeFunction::variadic("reduce", 2, |interpreter, args| {
+            expect_at_most(&args, 3, "reduce")?;
+            let items = list_arg(&args, 0, "reduce")?.borrow().clone();
+            let f = callable_arg(&args, 1, "reduce")?;
+            let mut items = items.into_iter();
+            let mut acc = match args.get(2) {
+                Some(initial) => initial.clone(),
+                None => items.next().ok_or_else(|| {
+                    ReefError::reef_native_error("reduce() of an empty list with no initial value")
+                })?,
+            };
+            for item in items {
+                acc = call(interpreter, &f, vec![acc, item], "reduce")?;
+            }
+            Ok(acc)
+        }),
+        // any(list, [predicate]) and all(list, [predicate]) stop at the first deciding element
+        NativeFunction::variadic("any", 1, |interpreter, args| {
+            expect_at_most(&args, 2, "any")?;
+            for item in list_arg(&args, 0, "any")?.borrow().clone() {
+                if test(interpreter, &args, item, "any")? {
+                    return Ok(Value::Boolean(true));
+                }
+            }
+            Ok(Value::Boolean(false))
+        }),
+        NativeFunction::variadic("all", 1, |interpreter, args| {
+            expect_at_most(&args, 2, "all")?;
+            for item in list_arg(&args, 0, "all")?.borrow().clone() {
+                if !test(interpreter, &args, item, "all")? {
+                    return Ok(Value::Boolean(false));
+                }
+            }
+            Ok(Value::Boolean(true))
+        }),
+        // pairs up elements, stopping at the shortest list
+        NativeFunction::variadic("zip", 1, |_interpreter, args| {
+            let lists = (0..args.len())
+                .map(|index| list_arg(&args, index, "zip").map(|list| list.borrow().clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let len = lists.iter().map(Vec::len).min().unwrap_or(0);
+            let rows = (0..len)
+                .map(|i| Value::new_list(lists.iter().map(|list| list[i].clone()).collect()))
+                .collect();
+            Ok(Value::new_list(rows))
+        }),
+        NativeFunction::new("enumerate", 1, |_interpreter, args| {
+            let items = list_arg(&args, 0, "enumerate")?.borrow().clone();
+            let pairs = items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| Value::new_list(vec![Value::Int(i as i64), item]))
+                .collect();
+            Ok(Value::new_list(pairs))
+        }),
+        // range(stop), range(start, stop) or range(start, stop, step), excluding stop
+        NativeFunction::variadic("range", 1, |_interpreter, args| {
+            expect_at_most(&args, 3, "range")?;
+            let (start, stop) = match args.len() {
+                1 => (0, int_arg(&args, 0, "range")?),
+                _ => (int_arg(&args, 0, "range")?, int_arg(&args, 1, "range")?),
+            };
+            let step = match args.get(2) {
+                Some(_) => int_arg(&args, 2, "range")?,
+                None => 1,
+            };
+            if step == 0 {
+                return Err(ReefError::reef_native_error(
+                    "range() step must not be zero",
+                ));
+            }
+            // widened so the span can't overflow; ceil((stop - start) / step), or 0 when empty
+            let (span, step_size) = (stop as i128 - start as i128, step as i128);
+            let len = if span.signum() == step_size.signum() {
+                (span.abs() + step_size.abs() - 1) / step_size.abs()
+            } else {
+                0
+            };
+            if len > MAX_LEN {
+                return Err(ReefError::reef_native_error(&format!(
+                    "range() would have more than {} items",
+                    MAX_LEN
+                )));
+            }
+            let mut numbers = Vec::with_capacity(len as usize);
+            let mut n = start;
+            while (step > 0 && n < stop) || (step < 0 && n > stop) {
+                numbers.push(Value::Int(n));
+                match n.checked_add(step) {
+                    Some(next) => n = next,
+                    None => break,
+                }
+            }
+            Ok(Value::new_list(numbers))
+        }),
+        NativeFunction::new("sum", 1, |_interpreter, args| {
+            let items = list_arg(&args, 0, "sum")?.borrow().clone();
+            items.iter().try_fold(Value::Int(0), |total, item| {
+                if item.as_float().is_none() {
+                    return Err(ReefError::reef_native_error(&format!(
+                        "sum() expects a list of numbers, got {}",
+                        item.type_name()
+                    )));
+                }
+                number::arithmetic(TokenType::Plus, &total, item)
+                    .map_err(|message| ReefError::reef_native_error(&message))
+            })
+        }),
+        // stable; `cmp(a, b)` returns a negative, zero or positive number
+        NativeFunction::variadic("sort", 1, |interpreter, args| {
+            expect_at_most(&args, 2, "sort")?;
+            let mut items = list_arg(&args, 0, "sort")?.borrow().clone();
+            let cmp = match args.get(1) {
+                Some(_) => Some(callable_arg(&args, 1, "sort")?),
+                None => None,
+            };
+            sort_by(&mut items, |a, b| match &cmp {
+                Some(cmp) => {
+                    let result = call(interpreter, cmp, vec![a.clone(), b.clone()], "sort")?;
+                    let n = result.as_float().ok_or_else(|| {
+                        ReefError::reef_native_error(&format!(
+                            "sort() comparator must return a number, got {}",
+                            result.type_name()
+                        ))
+                    })?;
+                    Ok(n.partial_cmp(&0.0).unwrap_or(Ordering::Equal))
+                }
+                None => natural_order(a, b, "sort"),
+            })?;
+            Ok(Value::new_list(items))
+        }),
+        // stable; each key is computed once
+        NativeFunction::new("sortBy", 2, |interpreter, args| {
+            let items = list_arg(&args, 0, "sortBy")?.borrow().clone();
+            let key = callable_arg(&args, 1, "sortBy")?;
+            let mut keyed = items
+                .into_iter()
+                .map(|item| Ok((call(interpreter, &key, vec![item.clone()], "sortBy")?, item)))
+                .collect::<Result<Vec<_>, ReefError>>()?;
+            sort_by(&mut keyed, |(a, _), (b, _)| natural_order(a, b, "sortBy"))?;
+            Ok(Value::new_list(
+                keyed.into_iter().map(|(_, item)| item).collect(),
+            ))
+        }),
+    ];
+    for native in natives {
+        let name = native.name.clone();
+        globals
+            .borrow_mut()
+            .define(name, Value::Callable(Rc::new(native)))
+            .expect("expect collection function to be definable");
+    }
+}
+
+fn callable_arg(
+    args: &[Value],
+    index: usize,
+    function: &str,
+) -> Result<Rc<dyn ReefCallable>, ReefError> {
+    match &args[index] {
+        Value::Callable(callable) => Ok(Rc::clone(callable)),
+        other => Err(type_error(function, index, "a function", other)),
+    }
+}
+
+// the truthiness of the element, or of the predicate applied to it
+fn test(
+    interpreter: &mut Interpreter,
+    args: &[Value],
+    item: Value,
+    function: &str,
+) -> Result<bool, ReefError> {
+    match args.get(1) {
+        Some(_) => {
+            let predicate = callable_arg(args, 1, function)?;
+            Ok(call(interpreter, &predicate, vec![item], function)?.is_truthy())
+        }
+        None => Ok(item.is_truthy()),
+    }
+}
+
+// numbers compare numerically and strings lexicographically; anything else is an error
+fn natural_order(a: &Value, b: &Value, function: &str) -> Result<Ordering, ReefError> {
+    if let (Value::String(l), Value::String(r)) = (a, b) {
+        return Ok(l.cmp(r));
+    }
+    match number::compare(a, b) {
+        Some(Some(ordering)) => Ok(ordering),
+        _ => Err(ReefError::reef_native_error(&format!(
+            "{}() cannot order {} and {}",
+            function,
+            a.type_name(),
+            b.type_name()
+        ))),
+    }
+}
+
+// std's stable sort with a fallible comparator: the first error ends the comparisons and is returned
+fn sort_by<T>(
+    items: &mut [T],
+    mut compare: impl FnMut(&T, &T) -> Result<Ordering, ReefError>,
+) -> Result<(), ReefError> {
+    let mut failure = None;
+    items.sort_by(|a, b| {
+        if failure.is_some() {
+            return Ordering::Equal;
+        }
+        compare(a, b).unwrap_or_else(|error| {
+            failure = Some(error);
+            Ordering::Equal
+        })
+    });
+    failure.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::ReefError, testing};
+
+    #[test]
+    fn higher_order_functions() {
+        let interpreter = testing::run(
+            "
+            fun double(x) { return x * 2; }
+            fun even(x) { return x - (x / 2) * 2 == 0; }
+            fun add(a, b) { return a + b; }
+            var doubled = map([1, 2, 3], double);
+            var evens = filter(range(10), even);
+            var total = reduce([1, 2, 3, 4], add);
+            var seeded = reduce([], add, 10);
+            var checks = [any([0, nil, 3]), all([1, 2], even), any([], even), all([])];
+            var pairs = zip([1, 2, 3], [\"a\", \"b\"]);
+            var indexed = enumerate([\"x\", \"y\"]);
+            var down = range(5, 0, -2);
+            var sums = [sum([1, 2, 3]), sum([1, 0.5]), sum([])];
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("doubled"), "[2, 4, 6]");
+        assert_eq!(render("evens"), "[0, 2, 4, 6, 8]");
+        assert_eq!(render("total"), "10");
+        assert_eq!(render("seeded"), "10");
+        assert_eq!(render("checks"), "[true, false, false, true]");
+        assert_eq!(render("pairs"), r#"[[1, "a"], [2, "b"]]"#);
+        assert_eq!(render("indexed"), r#"[[0, "x"], [1, "y"]]"#);
+        assert_eq!(render("down"), "[5, 3, 1]");
+        assert_eq!(render("sums"), "[6, 1.5, 0]");
+    }
+
+    #[test]
+    fn sorts_are_stable() {
+        let interpreter = testing::run(
+            "
+            fun byFirst(a, b) { return a[0] - b[0]; }
+            fun second(pair) { return pair[1]; }
+            var pairs = [[2, \"b\"], [1, \"z\"], [2, \"a\"], [1, \"y\"]];
+            var byCmp = sort(pairs, byFirst);
+            var byKey = sortBy(pairs, second);
+            var plain = sort([3, 1.5, 2]);
+            var words = sort([\"pear\", \"apple\"]);
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(
+            render("byCmp"),
+            r#"[[1, "z"], [1, "y"], [2, "b"], [2, "a"]]"#
+        );
+        assert_eq!(
+            render("byKey"),
+            r#"[[2, "a"], [2, "b"], [1, "y"], [1, "z"]]"#
+        );
+        assert_eq!(
+            render("pairs"),
+            r#"[[2, "b"], [1, "z"], [2, "a"], [1, "y"]]"#
+        );
+        assert_eq!(render("plain"), "[1.5, 2, 3]");
+        assert_eq!(render("words"), r#"["apple", "pear"]"#);
+    }
+
+    #[test]
+    fn callback_errors_propagate() {
+        for source in [
+            "fun bad(x) { return x + nil; } map([1], bad);",
+            "fun bad(a, b) { return nil < 1; } sort([1, 2], bad);",
+            "fun one(x) { return x; } reduce([1, 2], one);",
+            "sort([1, \"a\"]);",
+            "range(1, 2, 0);",
+            "range(9223372036854775807);",
+            "range(-9223372036854775807, 9223372036854775807, 2);",
+        ] {
+            let result = testing::run(source);
+            assert!(
+                matches!(result, Err(ReefError::RuntimeError(..))),
+                "{} should fail at runtime",
+                source
+            );
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Cursor},
+    mem,
+};
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    interpreter::Interpreter,
+    map::ReefMap,
+    module::ReefModule,
+    stdlib::{call, list_arg, string_arg, type_error},
+};
+
+pub fn module() -> ReefModule {
+    let csv = ReefModule::new("csv");
+    // csv.parse(text, [{header, delimiter}]); with a header row each record becomes a map
+    csv.define_native(NativeFunction::variadic(
+        "parse",
+        1,
+        |_interpreter, args| {
+            let text = string_arg(&args, 0, "csv.parse")?;
+            let options = Options::from_args(&args, 1, "csv.parse")?;
+            let mut rows = Rows::new(Cursor::new(text), options);
+            let mut parsed = Vec::new();
+            while let Some(row) = rows
+                .next_row()
+                .map_err(|message| csv_error("csv.parse", &message))?
+            {
+                parsed.push(row);
+            }
+            Ok(Value::new_list(parsed))
+        },
+    ));
+    // csv.eachRow(path, f, [options]) reads one record at a time and returns how many it saw
+    csv.define_native(NativeFunction::variadic(
+        "eachRow",
+        2,
+        |interpreter, args| {
+            let path = string_arg(&args, 0, "csv.eachRow")?;
+            let Value::Callable(callback) = &args[1] else {
+                return Err(type_error("csv.eachRow", 1, "a function", &args[1]));
+            };
+            let options = Options::from_args(&args, 2, "csv.eachRow")?;
+            let file = File::open(path)
+                .map_err(|e| csv_error("csv.eachRow", &format!("failed for '{}': {}", path, e)))?;
+            let mut rows = Rows::new(BufReader::new(file), options);
+            let mut count = 0;
+            while let Some(row) = rows
+                .next_row()
+                .map_err(|message| csv_error("csv.eachRow", &message))?
+            {
+                call(interpreter, callback, vec![row], "csv.eachRow")?;
+                count += 1;
+            }
+            Ok(Value::Int(count))
+        },
+    ));
+    // rows of lists, or of maps whose keys become the header in the order they first appear
+    csv.define_native(NativeFunction::variadic(
+        "stringify",
+        1,
+        |interpreter, args| {
+            let rows = list_arg(&args, 0, "csv.stringify")?.borrow().clone();
+            let options = Options::from_args(&args, 1, "csv.stringify")?;
+            stringify(interpreter, &rows, options.delimiter).map(Value::String)
+        },
+    ));
+    csv
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Options {
+    header: bool,
+    delimiter: char,
+}
+
+impl Options {
+    fn from_args(args: &[Value], index: usize, function: &str) -> Result<Self, ReefError> {
+        let mut options = Options {
+            header: false,
+            delimiter: ',',
+        };
+        if args.len() > index + 1 {
+            return Err(csv_error(
+                function,
+                &format!("expects at most {} args, got {}", index + 1, args.len()),
+            ));
+        }
+        let Some(given) = args.get(index) else {
+            return Ok(options);
+        };
+        let Value::Map(given) = given else {
+            return Err(type_error(function, index, "a map of options", given));
+        };
+        for (key, value) in given.borrow().iter() {
+            let Value::String(key) = key else {
+                return Err(type_error(function, index, "string option names", key));
+            };
+            match (key.as_str(), value) {
+                ("header", Value::Boolean(header)) => options.header = *header,
+                ("delimiter", Value::String(delimiter))
+                    if delimiter.chars().count() == 1
+                        && !matches!(delimiter.as_str(), "\"" | "\r" | "\n") =>
+                {
+                    options.delimiter = delimiter.chars().next().expect("one char");
+                }
+                ("header" | "delimiter", _) => {
+                    return Err(csv_error(
+                        function,
+                        &format!("has an invalid '{}' option", key),
+                    ));
+                }
+                _ => return Err(csv_error(function, &format!("has no option '{}'", key))),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn csv_error(function: &str, message: &str) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() {}", function, message))
+}
+
+// records as reef values, turning them into maps once a header has been read
+struct Rows<R> {
+    records: Records<R>,
+    header: Option<Vec<String>>,
+    wants_header: bool,
+}
+
+impl<R: BufRead> Rows<R> {
+    fn new(reader: R, options: Options) -> Self {
+        Rows {
+            records: Records {
+                reader,
+                delimiter: options.delimiter,
+                line: 0,
+            },
+            header: None,
+            wants_header: options.header,
+        }
+    }
+
+    fn next_row(&mut self) -> Result<Option<Value>, String> {
+        if self.wants_header && self.header.is_none() {
+            match self.records.next_record()? {
+                Some(header) => self.header = Some(header),
+                None => return Ok(None),
+            }
+        }
+        let Some(fields) = self.records.next_record()? else {
+            return Ok(None);
+        };
+        let Some(header) = &self.header else {
+            return Ok(Some(Value::new_list(
+                fields.into_iter().map(Value::String).collect(),
+            )));
+        };
+        if fields.len() > header.len() {
+            return Err(format!(
+                "line {} has {} fields but the header has {}",
+                self.records.line,
+                fields.len(),
+                header.len()
+            ));
+        }
+        // short rows leave the trailing columns nil
+        let mut fields = fields.into_iter();
+        let row: ReefMap = header
+            .iter()
+            .map(|key| (key.clone(), fields.next().map_or(Value::Nil, Value::String)))
+            .collect();
+        Ok(Some(Value::new_map(row)))
+    }
+}
+
+// RFC 4180 records; quoted fields may hold delimiters, doubled quotes and line breaks
+struct Records<R> {
+    reader: R,
+    delimiter: char,
+    line: usize,
+}
+
+impl<R: BufRead> Records<R> {
+    fn next_record(&mut self) -> Result<Option<Vec<String>>, String> {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut quoted = false;
+        loop {
+            let mut line = String::new();
+            let read = self
+                .reader
+                .read_line(&mut line)
+                .map_err(|e| format!("failed to read: {}", e))?;
+            if read == 0 {
+                if in_quotes {
+                    return Err(format!(
+                        "line {} has an unterminated quoted field",
+                        self.line
+                    ));
+                }
+                return Ok(None);
+            }
+            self.line += 1;
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                if in_quotes {
+                    match c {
+                        '"' if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        '"' => in_quotes = false,
+                        c => field.push(c),
+                    }
+                    continue;
+                }
+                match c {
+                    c if c == self.delimiter => {
+                        fields.push(mem::take(&mut field));
+                        quoted = false;
+                    }
+                    '\r' if chars.peek() == Some(&'\n') => {}
+                    '\n' => {}
+                    '"' if field.is_empty() && !quoted => {
+                        in_quotes = true;
+                        quoted = true;
+                    }
+                    _ if quoted => {
+                        return Err(format!("line {} has text after a closing quote", self.line));
+                    }
+                    '"' => {
+                        return Err(format!(
+                            "line {} has a quote inside an unquoted field",
+                            self.line
+                        ));
+                    }
+                    c => field.push(c),
+                }
+            }
+            if in_quotes {
+                continue;
+            }
+            // blank lines separate nothing, so they're skipped
+            if fields.is_empty() && field.is_empty() && !quoted {
+                continue;
+            }
+            fields.push(field);
+            return Ok(Some(fields));
+        }
+    }
+}
+
+fn stringify(
+    interpreter: &mut Interpreter,
+    rows: &[Value],
+    delimiter: char,
+) -> Result<String, ReefError> {
+    // later rows may add columns, so the header covers every row's keys
+    let header: Option<Vec<Value>> = match rows.first() {
+        Some(Value::Map(_)) => {
+            let mut columns = ReefMap::new();
+            for row in rows {
+                if let Value::Map(entries) = row {
+                    for (key, _) in entries.borrow().iter() {
+                        columns
+                            .insert_value(key.clone(), Value::Nil)
+                            .expect("map keys are always keys");
+                    }
+                }
+            }
+            Some(columns.iter().map(|(key, _)| key.clone()).collect())
+        }
+        _ => None,
+    };
+    let mut out = String::new();
+    if let Some(header) = &header {
+        let names = header.iter().map(|key| field_text(interpreter, key));
+        write_record(&mut out, names, delimiter);
+    }
+    for row in rows {
+        let fields: Vec<String> = match (row, &header) {
+            (Value::List(fields), None) => fields
+                .borrow()
+                .iter()
+                .map(|field| field_text(interpreter, field))
+                .collect(),
+            (Value::Map(entries), Some(header)) => {
+                let entries = entries.borrow();
+                header
+                    .iter()
+                    .map(|key| {
+                        entries
+                            .get_value(key)
+                            .ok()
+                            .flatten()
+                            .map_or(String::new(), |field| field_text(interpreter, field))
+                    })
+                    .collect()
+            }
+            _ => {
+                return Err(csv_error(
+                    "csv.stringify",
+                    &format!(
+                        "expects rows that are all lists or all maps, got {}",
+                        row.type_name()
+                    ),
+                ));
+            }
+        };
+        write_record(&mut out, fields.into_iter(), delimiter);
+    }
+    Ok(out)
+}
+
+// nil is written as an empty field
+fn field_text(interpreter: &Interpreter, field: &Value) -> String {
+    match field {
+        Value::Nil => String::new(),
+        other => interpreter.stringify(other),
+    }
+}
+
+fn write_record(out: &mut String, fields: impl Iterator<Item = String>, delimiter: char) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.push(delimiter);
+        }
+        if field.contains([delimiter, '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&field);
+        }
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::Records;
+    use crate::testing;
+
+    fn records(text: &str) -> Result<Vec<Vec<String>>, String> {
+        let mut records = Records {
+            reader: Cursor::new(text),
+            delimiter: ',',
+            line: 0,
+        };
+        let mut found = Vec::new();
+        while let Some(record) = records.next_record()? {
+            found.push(record);
+        }
+        Ok(found)
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        assert_eq!(
+            records("a,\"b,c\",\"say \"\"hi\"\"\"\r\n\n\"multi\nline\",,x").expect("valid csv"),
+            [vec!["a", "b,c", "say \"hi\""], vec!["multi\nline", "", "x"],]
+        );
+        assert!(records("\"open").is_err());
+        assert!(records("\"closed\"x").is_err());
+        assert!(records("mid\"quote").is_err());
+    }
+
+    #[test]
+    fn parses_and_writes_rows() {
+        let dir = std::env::temp_dir().join(format!("reef-csv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir should be creatable");
+        let path = dir.join("people.tsv");
+        std::fs::write(&path, "name\tage\nada\t36\nalan\n").expect("temp file should be writable");
+        let source = format!(
+            "
+            var plain = csv.parse(\"a,b
+1,2
+\");
+            var people = [];
+            fun keep(row) {{ people.push(row); }}
+            var count = csv.eachRow(\"{}\", keep, {{\"header\": true, \"delimiter\": \"\t\"}});
+            var written = csv.stringify([[\"x,y\", 1, nil], [\"line
+break\", 2.5, true]]);
+            var fromMaps = csv.stringify(people, {{\"delimiter\": \";\"}});
+            var roundTrip = csv.parse(written);
+        ",
+            path.to_str()
+                .expect("temp path is utf-8")
+                .replace('\\', "/")
+        );
+        let interpreter = testing::run(&source).expect("source should run");
+        std::fs::remove_dir_all(&dir).expect("temp dir should be removable");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("plain"), r#"[["a", "b"], ["1", "2"]]"#);
+        assert_eq!(render("count"), "2");
+        assert_eq!(
+            render("people"),
+            r#"[{"name": "ada", "age": "36"}, {"name": "alan", "age": nil}]"#
+        );
+        assert_eq!(render("written"), "\"x,y\",1,\n\"line\nbreak\",2.5,true\n");
+        assert_eq!(render("fromMaps"), "name;age\nada;36\nalan;\n");
+        assert_eq!(
+            render("roundTrip"),
+            r#"[["x,y", "1", ""], ["line\nbreak", "2.5", "true"]]"#
+        );
+    }
+
+    #[test]
+    fn header_covers_every_row() {
+        let interpreter = testing::run(
+            "
+            var written = csv.stringify([{\"a\": 1}, {\"b\": 2, \"a\": 3}, {\"c\": 4}]);
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let written = interpreter.stringify(&globals.get("written").expect("defined"));
+        assert_eq!(written, "a,b,c\n1,,\n3,2,\n,,4\n");
+    }
+}
@@ -0,0 +1,153 @@
+use bytes::Bytes;
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    module::ReefModule,
+    stdlib::{
+        bytes::{base64_decode, base64_encode, hex_decode, hex_encode},
+        data_arg, expect_at_most, string_arg, type_error,
+    },
+};
+
+pub fn module() -> ReefModule {
+    let encoding = ReefModule::new("encoding");
+    // base64Encode(data, [urlSafe]); the url-safe alphabet is written without padding
+    encoding.define_native(NativeFunction::variadic(
+        "base64Encode",
+        1,
+        |_interpreter, args| {
+            let data = data_arg(&args, 0, "encoding.base64Encode")?;
+            let url_safe = url_safe_arg(&args, "encoding.base64Encode")?;
+            Ok(Value::String(base64_encode(&data, url_safe)))
+        },
+    ));
+    encoding.define_native(NativeFunction::variadic(
+        "base64Decode",
+        1,
+        |_interpreter, args| {
+            let text = string_arg(&args, 0, "encoding.base64Decode")?;
+            let url_safe = url_safe_arg(&args, "encoding.base64Decode")?;
+            base64_decode(text, url_safe)
+                .map(|buf| Value::Bytes(Bytes::from(buf)))
+                .map_err(|message| decode_error("encoding.base64Decode", &message))
+        },
+    ));
+    encoding.define_native(NativeFunction::new("hexEncode", 1, |_interpreter, args| {
+        let data = data_arg(&args, 0, "encoding.hexEncode")?;
+        Ok(Value::String(hex_encode(&data)))
+    }));
+    encoding.define_native(NativeFunction::new("hexDecode", 1, |_interpreter, args| {
+        let text = string_arg(&args, 0, "encoding.hexDecode")?;
+        hex_decode(text)
+            .map(|buf| Value::Bytes(Bytes::from(buf)))
+            .map_err(|message| decode_error("encoding.hexDecode", &message))
+    }));
+    encoding.define_native(NativeFunction::new("urlEncode", 1, |_interpreter, args| {
+        let data = data_arg(&args, 0, "encoding.urlEncode")?;
+        Ok(Value::String(percent_encode(&data)))
+    }));
+    encoding.define_native(NativeFunction::new("urlDecode", 1, |_interpreter, args| {
+        let text = string_arg(&args, 0, "encoding.urlDecode")?;
+        let buf =
+            percent_decode(text).map_err(|message| decode_error("encoding.urlDecode", &message))?;
+        String::from_utf8(buf)
+            .map(Value::String)
+            .map_err(|_| decode_error("encoding.urlDecode", "produced invalid utf-8"))
+    }));
+    encoding
+}
+
+fn url_safe_arg(args: &[Value], function: &str) -> Result<bool, ReefError> {
+    expect_at_most(args, 2, function)?;
+    match args.get(1) {
+        None => Ok(false),
+        Some(Value::Boolean(url_safe)) => Ok(*url_safe),
+        Some(other) => Err(type_error(function, 1, "a boolean", other)),
+    }
+}
+
+fn decode_error(function: &str, message: &str) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() {}", function, message))
+}
+
+// everything but the RFC 3986 unreserved characters is escaped, spaces included
+pub fn percent_encode(data: &[u8]) -> String {
+    let mut out = String::new();
+    for byte in data {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(*byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+pub fn percent_decode(text: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut bytes = text.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            out.push(byte);
+            continue;
+        }
+        let escape: Vec<u8> = bytes.by_ref().take(2).collect();
+        let decoded = std::str::from_utf8(&escape)
+            .ok()
+            .filter(|hex| hex.len() == 2 && hex.bytes().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| {
+                format!(
+                    "has an invalid escape '%{}'",
+                    String::from_utf8_lossy(&escape)
+                )
+            })?;
+        out.push(decoded);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{percent_decode, percent_encode};
+    use crate::testing;
+
+    #[test]
+    fn encodes_and_decodes() {
+        let interpreter = testing::run(
+            "
+            var standard = encoding.base64Encode(\"any carnal pleas\");
+            var urlSafe = encoding.base64Encode(b\"\\xfb\\xff\", true);
+            var decoded = encoding.base64Decode(urlSafe, true);
+            var hex = encoding.hexEncode(\"hi\");
+            var unhex = encoding.hexDecode(\"6869\").decode();
+            var query = encoding.urlEncode(\"a b&c=d/é~\");
+            var unquoted = encoding.urlDecode(query);
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("standard"), "YW55IGNhcm5hbCBwbGVhcw==");
+        assert_eq!(render("urlSafe"), "-_8");
+        assert_eq!(render("decoded"), r#"b"\xfb\xff""#);
+        assert_eq!(render("hex"), "6869");
+        assert_eq!(render("unhex"), "hi");
+        assert_eq!(render("query"), "a%20b%26c%3Dd%2F%C3%A9~");
+        assert_eq!(render("unquoted"), "a b&c=d/é~");
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(percent_encode(b"\x00"), "%00");
+        assert!(percent_decode("%4").is_err());
+        assert!(percent_decode("%zz").is_err());
+        assert!(percent_decode("%+f").is_err());
+        assert!(testing::run("encoding.urlDecode(\"%ff\");").is_err());
+        assert!(testing::run("encoding.hexDecode(\"abc\");").is_err());
+        assert!(testing::run("encoding.base64Decode(\"Zm9v\", 1);").is_err());
+    }
+}
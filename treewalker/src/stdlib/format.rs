@@ -0,0 +1,320 @@
+use std::rc::Rc;
+
+use crate::{
+    Value,
+    environment::EnvRef,
+    error::ReefError,
+    func::NativeFunction,
+    interpreter::Interpreter,
+    stdlib::{call, string_arg},
+};
+
+pub fn define_natives(globals: &EnvRef) {
+    // format("{:>8.2} {name}", x, {"name": "reef"}); named placeholders read the trailing map
+    let format = NativeFunction::variadic("format", 1, |interpreter, args| {
+        let template = string_arg(&args, 0, "format")?;
+        format(interpreter, template, &args[1..]).map(Value::String)
+    });
+    globals
+        .borrow_mut()
+        .define("format".to_string(), Value::Callable(Rc::new(format)))
+        .expect("expect format function to be definable");
+}
+
+// `{[argument][:[[fill]align][+][#][0][width][.precision][type]]}`, as in Rust's std::fmt
+#[derive(Debug, Default)]
+struct Spec {
+    fill: Option<char>,
+    align: Option<char>,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    kind: Option<char>,
+}
+
+// std's formatter refuses larger precisions, and widths share the cap so padding stays small
+const MAX_WIDTH: usize = u16::MAX as usize;
+
+fn format_error(message: &str) -> ReefError {
+    ReefError::reef_native_error(&format!("format() {}", message))
+}
+
+pub fn format(
+    interpreter: &mut Interpreter,
+    template: &str,
+    args: &[Value],
+) -> Result<String, ReefError> {
+    let mut out = String::new();
+    let mut chars = template.chars().peekable();
+    let mut next_positional = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '}' => return Err(format_error("unmatched '}' in template")),
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err(format_error("unmatched '{' in template")),
+                    }
+                }
+                let (argument, spec) = match placeholder.split_once(':') {
+                    Some((argument, spec)) => (argument, parse_spec(spec)?),
+                    None => (placeholder.as_str(), Spec::default()),
+                };
+                let value = lookup(argument, args, &mut next_positional)?;
+                out.push_str(&render(interpreter, &value, &spec)?);
+            }
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+fn lookup(argument: &str, args: &[Value], next_positional: &mut usize) -> Result<Value, ReefError> {
+    let index = if argument.is_empty() {
+        *next_positional += 1;
+        *next_positional - 1
+    } else if let Ok(index) = argument.parse::<usize>() {
+        index
+    } else {
+        return match args.last() {
+            Some(Value::Map(named)) => named
+                .borrow()
+                .get(argument)
+                .cloned()
+                .ok_or_else(|| format_error(&format!("has no named argument '{}'", argument))),
+            _ => Err(format_error(&format!(
+                "named placeholder '{}' needs a map as the last argument",
+                argument
+            ))),
+        };
+    };
+    args.get(index)
+        .cloned()
+        .ok_or_else(|| format_error(&format!("has no argument {}", index)))
+}
+
+fn parse_spec(spec: &str) -> Result<Spec, ReefError> {
+    let chars: Vec<char> = spec.chars().collect();
+    let mut result = Spec::default();
+    let mut i = 0;
+    let is_align = |c: Option<&char>| matches!(c, Some('<' | '^' | '>'));
+    if is_align(chars.get(1)) {
+        result.fill = Some(chars[0]);
+        result.align = Some(chars[1]);
+        i = 2;
+    } else if is_align(chars.first()) {
+        result.align = Some(chars[0]);
+        i = 1;
+    }
+    if chars.get(i) == Some(&'+') {
+        result.plus = true;
+        i += 1;
+    }
+    if chars.get(i) == Some(&'#') {
+        result.alternate = true;
+        i += 1;
+    }
+    if chars.get(i) == Some(&'0') {
+        result.zero = true;
+        i += 1;
+    }
+    let width = take_digits(&chars, &mut i, spec)?;
+    result.width = width.unwrap_or(0);
+    if chars.get(i) == Some(&'.') {
+        i += 1;
+        result.precision = Some(
+            take_digits(&chars, &mut i, spec)?
+                .ok_or_else(|| format_error(&format!("missing precision in '{}'", spec)))?,
+        );
+    }
+    match &chars[i..] {
+        [] => {}
+        [kind @ ('x' | 'X' | 'b' | 'o' | 'e' | 'E')] => result.kind = Some(*kind),
+        _ => return Err(format_error(&format!("invalid format spec '{}'", spec))),
+    }
+    Ok(result)
+}
+
+// None when there are no digits; too many is an error rather than a silent zero
+fn take_digits(chars: &[char], i: &mut usize, spec: &str) -> Result<Option<usize>, ReefError> {
+    let start = *i;
+    while chars.get(*i).is_some_and(char::is_ascii_digit) {
+        *i += 1;
+    }
+    if start == *i {
+        return Ok(None);
+    }
+    let digits: String = chars[start..*i].iter().collect();
+    match digits.parse() {
+        Ok(n) if n <= MAX_WIDTH => Ok(Some(n)),
+        _ => Err(format_error(&format!(
+            "width and precision can't exceed {} in '{}'",
+            MAX_WIDTH, spec
+        ))),
+    }
+}
+
+fn render(interpreter: &mut Interpreter, value: &Value, spec: &Spec) -> Result<String, ReefError> {
+    let is_number = value.as_float().is_some();
+    // numbers are split into sign, radix prefix and digits so zero padding goes in between
+    let (sign, prefix, body) = match (spec.kind, value) {
+        (Some(kind @ ('x' | 'X' | 'b' | 'o')), Value::Int(n)) => {
+            let digits = match kind {
+                'x' => format!("{:x}", n.unsigned_abs()),
+                'X' => format!("{:X}", n.unsigned_abs()),
+                'b' => format!("{:b}", n.unsigned_abs()),
+                _ => format!("{:o}", n.unsigned_abs()),
+            };
+            let prefix = match kind {
+                _ if !spec.alternate => "",
+                'x' | 'X' => "0x",
+                'b' => "0b",
+                _ => "0o",
+            };
+            (sign_of(*n < 0, spec.plus), prefix, digits)
+        }
+        (Some('x' | 'X' | 'b' | 'o'), _) => {
+            return Err(format_error(&format!(
+                "radix formatting expects an int, got {}",
+                value.type_name()
+            )));
+        }
+        (Some(kind @ ('e' | 'E')), _) => {
+            let n = value.as_float().ok_or_else(|| {
+                format_error(&format!(
+                    "exponent formatting expects a number, got {}",
+                    value.type_name()
+                ))
+            })?;
+            let digits = match spec.precision {
+                Some(precision) => format!("{:.*e}", precision, n.abs()),
+                None => format!("{:e}", n.abs()),
+            };
+            let digits = if kind == 'E' {
+                digits.to_uppercase()
+            } else {
+                digits
+            };
+            (sign_of(n.is_sign_negative(), spec.plus), "", digits)
+        }
+        (_, _) if is_number && spec.precision.is_some() => {
+            let n = value.as_float().expect("checked numeric");
+            let precision = spec.precision.expect("checked precision");
+            let digits = format!("{:.*}", precision, n.abs());
+            (sign_of(n.is_sign_negative(), spec.plus), "", digits)
+        }
+        (_, _) if is_number => {
+            let text = interpreter.stringify(value);
+            match text.strip_prefix('-') {
+                Some(digits) => ("-", "", digits.to_string()),
+                None => (sign_of(false, spec.plus), "", text),
+            }
+        }
+        (_, _) => {
+            let text = display(interpreter, value)?;
+            let text = match spec.precision {
+                Some(precision) => text.chars().take(precision).collect(),
+                None => text,
+            };
+            ("", "", text)
+        }
+    };
+
+    let len = sign.chars().count() + prefix.chars().count() + body.chars().count();
+    let missing = spec.width.saturating_sub(len);
+    if spec.zero && is_number && spec.align.is_none() {
+        return Ok(format!("{}{}{}{}", sign, prefix, "0".repeat(missing), body));
+    }
+    let text = format!("{}{}{}", sign, prefix, body);
+    let fill = spec.fill.unwrap_or(' ').to_string();
+    let align = spec.align.unwrap_or(if is_number { '>' } else { '<' });
+    Ok(match align {
+        '<' => format!("{}{}", text, fill.repeat(missing)),
+        '>' => format!("{}{}", fill.repeat(missing), text),
+        _ => format!(
+            "{}{}{}",
+            fill.repeat(missing / 2),
+            text,
+            fill.repeat(missing - missing / 2)
+        ),
+    })
+}
+
+fn sign_of(negative: bool, plus: bool) -> &'static str {
+    match (negative, plus) {
+        (true, _) => "-",
+        (false, true) => "+",
+        (false, false) => "",
+    }
+}
+
+// instances with a `__str__` method format as whatever it returns
+fn display(interpreter: &mut Interpreter, value: &Value) -> Result<String, ReefError> {
+    if let Value::Instance(instance) = value
+        && let Some(method) = instance.bound_method(interpreter, "__str__")?
+    {
+        let text = call(interpreter, &method, Vec::new(), "format")?;
+        return Ok(interpreter.stringify(&text));
+    }
+    Ok(interpreter.stringify(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+
+    #[test]
+    fn formats_specifiers() {
+        let interpreter = testing::run(
+            "
+            class Point {
+                init(x, y) { this.x = x; this.y = y; }
+                __str__() { return format(\"({}, {})\", this.x, this.y); }
+            }
+            var columns = format(\"[{:>8.2}|{:<6}|{:^7}]\", 3.14159, \"ab\", \"mid\");
+            var radix = format(\"{:#x} {:08b} {:X} {:o}\", 255, 5, -255, 8);
+            var signs = format(\"{:+} {:+.1} {:05} {:*^9.3e}\", 7, -2.25, -42, 1234.5);
+            var named = format(\"{1}{0} {who} {{literal}}\", \"b\", \"a\", {\"who\": \"reef\"});
+            var shown = format(\"{} {:.3}\", Point(1, 2.5), \"truncate\");
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("columns"), "[    3.14|ab    |  mid  ]");
+        assert_eq!(render("radix"), "0xff 00000101 -FF 10");
+        assert_eq!(render("signs"), "+7 -2.2 -0042 *1.234e3*");
+        assert_eq!(render("named"), "ab reef {literal}");
+        assert_eq!(render("shown"), "(1, 2.5) tru");
+    }
+
+    #[test]
+    fn rejects_bad_templates() {
+        for source in [
+            "format(\"{\");",
+            "format(\"}\");",
+            "format(\"{} {}\", 1);",
+            "format(\"{:q}\", 1);",
+            "format(\"{:x}\", 1.5);",
+            "format(\"{name}\", 1);",
+            "format(\"{:99999999999999}\", 1);",
+            "format(\"{:.99999999999}\", 1.5);",
+            "format(\"{:99999999999999999999999}\", 1);",
+        ] {
+            assert!(testing::run(source).is_err(), "{} should fail", source);
+        }
+    }
+}
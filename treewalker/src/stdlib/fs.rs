@@ -0,0 +1,175 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::UNIX_EPOCH,
+};
+
+use bytes::Bytes;
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    map::ReefMap,
+    module::ReefModule,
+    stdlib::{bytes_arg, string_arg},
+};
+
+pub fn module() -> ReefModule {
+    let fs = ReefModule::new("fs");
+    fs.define_native(NativeFunction::new("readFile", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.readFile")?;
+        let contents = fs::read_to_string(path).map_err(|e| io_error("fs.readFile", path, e))?;
+        Ok(Value::String(contents))
+    }));
+    fs.define_native(NativeFunction::new("readLines", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.readLines")?;
+        let contents = fs::read_to_string(path).map_err(|e| io_error("fs.readLines", path, e))?;
+        let lines = contents
+            .lines()
+            .map(|line| Value::String(line.to_string()))
+            .collect();
+        Ok(Value::new_list(lines))
+    }));
+    fs.define_native(NativeFunction::new("readBytes", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.readBytes")?;
+        let contents = fs::read(path).map_err(|e| io_error("fs.readBytes", path, e))?;
+        Ok(Value::Bytes(Bytes::from(contents)))
+    }));
+    fs.define_native(NativeFunction::new("writeFile", 2, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.writeFile")?;
+        let contents = string_arg(&args, 1, "fs.writeFile")?;
+        fs::write(path, contents).map_err(|e| io_error("fs.writeFile", path, e))?;
+        Ok(Value::Nil)
+    }));
+    fs.define_native(NativeFunction::new(
+        "writeBytes",
+        2,
+        |_interpreter, args| {
+            let path = string_arg(&args, 0, "fs.writeBytes")?;
+            let contents = bytes_arg(&args, 1, "fs.writeBytes")?;
+            fs::write(path, contents).map_err(|e| io_error("fs.writeBytes", path, e))?;
+            Ok(Value::Nil)
+        },
+    ));
+    fs.define_native(NativeFunction::new(
+        "appendFile",
+        2,
+        |_interpreter, args| {
+            let path = string_arg(&args, 0, "fs.appendFile")?;
+            let contents = string_arg(&args, 1, "fs.appendFile")?;
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(contents.as_bytes()))
+                .map_err(|e| io_error("fs.appendFile", path, e))?;
+            Ok(Value::Nil)
+        },
+    ));
+    fs.define_native(NativeFunction::new("exists", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.exists")?;
+        Ok(Value::Boolean(Path::new(path).exists()))
+    }));
+    // removes a file or an empty directory; non-empty directories are an error
+    fs.define_native(NativeFunction::new("remove", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.remove")?;
+        let result = if Path::new(path).is_dir() {
+            fs::remove_dir(path)
+        } else {
+            fs::remove_file(path)
+        };
+        result.map_err(|e| io_error("fs.remove", path, e))?;
+        Ok(Value::Nil)
+    }));
+    fs.define_native(NativeFunction::new("mkdir", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.mkdir")?;
+        fs::create_dir_all(path).map_err(|e| io_error("fs.mkdir", path, e))?;
+        Ok(Value::Nil)
+    }));
+    fs.define_native(NativeFunction::new("listDir", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.listDir")?;
+        let mut names = fs::read_dir(path)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+                    .collect::<io::Result<Vec<String>>>()
+            })
+            .map_err(|e| io_error("fs.listDir", path, e))?;
+        // read_dir order is platform dependent
+        names.sort();
+        Ok(Value::new_list(
+            names.into_iter().map(Value::String).collect(),
+        ))
+    }));
+    fs.define_native(NativeFunction::new("stat", 1, |_interpreter, args| {
+        let path = string_arg(&args, 0, "fs.stat")?;
+        let metadata = fs::metadata(path).map_err(|e| io_error("fs.stat", path, e))?;
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|since| Value::Number(since.as_secs_f64()))
+            .unwrap_or(Value::Nil);
+        let stat: ReefMap = [
+            ("size".to_string(), Value::Int(metadata.len() as i64)),
+            ("mtime".to_string(), mtime),
+            ("isDir".to_string(), Value::Boolean(metadata.is_dir())),
+        ]
+        .into_iter()
+        .collect();
+        Ok(Value::new_map(stat))
+    }));
+    fs
+}
+
+fn io_error(function: &str, path: &str, error: io::Error) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() failed for '{}': {}", function, path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::ReefError, testing};
+
+    #[test]
+    fn files_round_trip() {
+        let dir = std::env::temp_dir().join(format!("reef-fs-{}", std::process::id()));
+        let dir = dir.to_str().expect("temp dir is utf-8").replace('\\', "/");
+        let source = format!(
+            "
+            var dir = \"{dir}\";
+            fs.mkdir(dir + \"/nested/deeper\");
+            fs.writeFile(dir + \"/notes.txt\", \"one\n\");
+            fs.appendFile(dir + \"/notes.txt\", \"two\n\");
+            var text = fs.readFile(dir + \"/notes.txt\");
+            var lines = fs.readLines(dir + \"/notes.txt\");
+            var listing = fs.listDir(dir);
+            var info = fs.stat(dir + \"/notes.txt\");
+            var size = info[\"size\"];
+            var isDir = fs.stat(dir + \"/nested\")[\"isDir\"];
+            fs.remove(dir + \"/notes.txt\");
+            var gone = !fs.exists(dir + \"/notes.txt\");
+            fs.writeBytes(dir + \"/blob.bin\", b\"\\x00\\xffok\");
+            var blob = fs.readBytes(dir + \"/blob.bin\");
+        "
+        );
+        let interpreter = testing::run(&source).expect("source should run");
+        std::fs::remove_dir_all(&dir).expect("temp dir should be removable");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("text"), "one\ntwo\n");
+        assert_eq!(render("lines"), r#"["one", "two"]"#);
+        assert_eq!(render("listing"), r#"["nested", "notes.txt"]"#);
+        assert_eq!(render("size"), "8");
+        assert_eq!(render("isDir"), "true");
+        assert_eq!(render("gone"), "true");
+        assert_eq!(render("blob"), r#"b"\x00\xffok""#);
+    }
+
+    #[test]
+    fn io_failures_are_runtime_errors() {
+        let result = testing::run("fs.readFile(\"/definitely/not/here.txt\");");
+        assert!(matches!(result, Err(ReefError::RuntimeError(_))));
+    }
+}
@@ -0,0 +1,604 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+};
+
+use bytes::Bytes;
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    interpreter::Interpreter,
+    map::ReefMap,
+    module::ReefModule,
+    stdlib::{
+        call, data_arg, expect_at_most, int_arg, net::port_arg, string_arg, sys::env_read_lock,
+        type_error,
+    },
+};
+
+type Headers = Vec<(String, String)>;
+
+// bodies larger than this are refused, so a peer's Content-Length can't exhaust memory
+const MAX_BODY: usize = 16 * 1024 * 1024;
+// likewise for the start line and each header line, and for how many headers there are
+const MAX_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+
+pub fn module() -> ReefModule {
+    let http = ReefModule::new("http");
+    // http.get(url, [headers]) returns {status, headers, body}
+    http.define_native(NativeFunction::variadic("get", 1, |_interpreter, args| {
+        expect_at_most(&args, 2, "http.get")?;
+        let url = string_arg(&args, 0, "http.get")?;
+        let headers = headers_arg(&args, 1, "http.get")?;
+        request("GET", url, &headers, None).map_err(|message| http_error("http.get", &message))
+    }));
+    // http.post(url, body, [headers]); the body is a string or bytes
+    http.define_native(NativeFunction::variadic("post", 2, |_interpreter, args| {
+        expect_at_most(&args, 3, "http.post")?;
+        let url = string_arg(&args, 0, "http.post")?;
+        let body = data_arg(&args, 1, "http.post")?;
+        let headers = headers_arg(&args, 2, "http.post")?;
+        request("POST", url, &headers, Some(body))
+            .map_err(|message| http_error("http.post", &message))
+    }));
+    // http.serve(port, handler, [{limit, maxBody}]) calls handler(request) once per request,
+    // forever unless `limit` requests have been answered; bigger bodies than `maxBody` get a 413
+    http.define_native(NativeFunction::variadic("serve", 2, |interpreter, args| {
+        expect_at_most(&args, 3, "http.serve")?;
+        let port = port_arg(&args, 0, "http.serve")?;
+        let Value::Callable(handler) = &args[1] else {
+            return Err(type_error("http.serve", 1, "a function", &args[1]));
+        };
+        let (limit, max_body) = match args.get(2) {
+            None => (None, MAX_BODY),
+            Some(Value::Map(options)) => {
+                let options = options.borrow();
+                let option = |name: &str| match options.get(name) {
+                    Some(value) => int_arg(std::slice::from_ref(value), 0, "http.serve").map(Some),
+                    None => Ok(None),
+                };
+                let limit = option("limit")?;
+                let max_body = match option("maxBody")? {
+                    Some(max_body) => usize::try_from(max_body)
+                        .map_err(|_| http_error("http.serve", "maxBody must not be negative"))?,
+                    None => MAX_BODY,
+                };
+                (limit, max_body)
+            }
+            Some(other) => return Err(type_error("http.serve", 2, "a map of options", other)),
+        };
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| http_error("http.serve", &format!("failed for port {}: {}", port, e)))?;
+        let mut served = 0;
+        while limit.is_none_or(|limit| served < limit) {
+            let (stream, _) = listener
+                .accept()
+                .map_err(|e| http_error("http.serve", &format!("accept failed: {}", e)))?;
+            served += 1;
+            let mut reader = BufReader::new(stream);
+            let request = match read_request(&mut reader, max_body) {
+                Ok(request) => request,
+                Err((status, message)) => {
+                    // a broken client shouldn't take the server down with it
+                    let _ = respond(reader.get_mut(), status, &[], message.as_bytes());
+                    continue;
+                }
+            };
+            match call(interpreter, handler, vec![request], "http.serve") {
+                Ok(response) => {
+                    let (status, headers, body) = response_parts(interpreter, response)?;
+                    let _ = respond(reader.get_mut(), status, &headers, &body);
+                }
+                Err(error) => {
+                    let _ = respond(reader.get_mut(), 500, &[], b"internal server error");
+                    return Err(error);
+                }
+            }
+        }
+        Ok(Value::Nil)
+    }));
+    http
+}
+
+fn http_error(function: &str, message: &str) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() {}", function, message))
+}
+
+fn headers_arg(args: &[Value], index: usize, function: &str) -> Result<Headers, ReefError> {
+    match args.get(index) {
+        None => Ok(Vec::new()),
+        Some(Value::Map(headers)) => headers
+            .borrow()
+            .iter()
+            .map(|(name, value)| match (name, value) {
+                // a line break would let a value smuggle in extra headers or a second message
+                (Value::String(name), Value::String(value))
+                    if name.contains(['\r', '\n', ':']) || value.contains(['\r', '\n']) =>
+                {
+                    Err(ReefError::reef_native_error(&format!(
+                        "{}() header {:?} can't contain a line break, or a ':' in its name",
+                        function, name
+                    )))
+                }
+                (Value::String(name), Value::String(value)) => Ok((name.clone(), value.clone())),
+                (Value::String(_), other) | (other, _) => Err(type_error(
+                    function,
+                    index,
+                    "string header names and values",
+                    other,
+                )),
+            })
+            .collect(),
+        Some(other) => Err(type_error(function, index, "a map of headers", other)),
+    }
+}
+
+// `http://host[:port][/path]`; there's no TLS, so https is refused
+fn parse_url(url: &str) -> Result<(String, u16, String), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("only supports http:// urls, got '{}'", url))?;
+    if url.contains(['\r', '\n']) {
+        return Err(format!("has a line break in {:?}", url));
+    }
+    let (authority, path) = match rest.find('/') {
+        Some(slash) => (&rest[..slash], &rest[slash..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse()
+                .map_err(|_| format!("has an invalid port in '{}'", url))?,
+        ),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(format!("has no host in '{}'", url));
+    }
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+fn request(
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+    body: Option<Bytes>,
+) -> Result<Value, String> {
+    let (host, port, path) = parse_url(url)?;
+    let mut stream = {
+        let _env = env_read_lock();
+        TcpStream::connect((host.as_str(), port))
+    }
+    .map_err(|e| format!("failed to connect to {}:{}: {}", host, port, e))?;
+    let mut head = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        method, path, host
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    if let Some(body) = &body {
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+    stream
+        .write_all(head.as_bytes())
+        .and_then(|_| stream.write_all(body.as_deref().unwrap_or_default()))
+        .map_err(|e| format!("failed to send: {}", e))?;
+
+    let mut reader = BufReader::new(stream);
+    let (status_line, headers) = read_head(&mut reader)
+        .map_err(|(_, message)| message)?
+        .ok_or("got no response")?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<i64>().ok())
+        .ok_or_else(|| format!("got a malformed status line '{}'", status_line))?;
+    let body = read_body(&mut reader, &headers, true, MAX_BODY).map_err(|(_, message)| message)?;
+    let response: ReefMap = [
+        ("status".to_string(), Value::Int(status)),
+        ("headers".to_string(), headers_value(headers)),
+        ("body".to_string(), body_value(body)),
+    ]
+    .into_iter()
+    .collect();
+    Ok(Value::new_map(response))
+}
+
+// {method, path, query, headers, body}; the query is the raw text after '?'
+// failures come with the status to answer them with
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Value, (u16, String)> {
+    let bad_request = |message: String| (400, message);
+    let (request_line, headers) =
+        read_head(reader)?.ok_or_else(|| bad_request("empty request".to_string()))?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(bad_request(format!(
+            "malformed request line '{}'",
+            request_line
+        )));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let body = read_body(reader, &headers, false, max_body)?;
+    let request: ReefMap = [
+        ("method".to_string(), Value::String(method.to_string())),
+        ("path".to_string(), Value::String(path.to_string())),
+        ("query".to_string(), Value::String(query.to_string())),
+        ("headers".to_string(), headers_value(headers)),
+        ("body".to_string(), body_value(body)),
+    ]
+    .into_iter()
+    .collect();
+    Ok(Value::new_map(request))
+}
+
+// a bare string or bytes is a 200; a map may set status, headers and body
+fn response_parts(
+    interpreter: &Interpreter,
+    response: Value,
+) -> Result<(u16, Headers, Vec<u8>), ReefError> {
+    let text_plain = || {
+        vec![(
+            "Content-Type".to_string(),
+            "text/plain; charset=utf-8".to_string(),
+        )]
+    };
+    let Value::Map(response) = response else {
+        let body = match response {
+            Value::Bytes(buf) => buf.to_vec(),
+            other => interpreter.stringify(&other).into_bytes(),
+        };
+        return Ok((200, text_plain(), body));
+    };
+    let response = response.borrow();
+    let status = match response.get("status") {
+        None => 200,
+        Some(Value::Int(status)) if (100..600).contains(status) => *status as u16,
+        Some(other) => {
+            return Err(http_error(
+                "http.serve",
+                &format!(
+                    "handler returned an invalid status {}",
+                    interpreter.stringify(other)
+                ),
+            ));
+        }
+    };
+    let mut headers = match response.get("headers") {
+        Some(headers) => headers_arg(std::slice::from_ref(headers), 0, "http.serve")?,
+        None => Vec::new(),
+    };
+    if !headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+    {
+        headers.extend(text_plain());
+    }
+    let body = match response.get("body") {
+        None | Some(Value::Nil) => Vec::new(),
+        Some(Value::Bytes(buf)) => buf.to_vec(),
+        Some(other) => interpreter.stringify(other).into_bytes(),
+    };
+    Ok((status, headers, body))
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: u16,
+    headers: &[(String, String)],
+    body: &[u8],
+) -> io::Result<()> {
+    let mut head = format!("HTTP/1.1 {} {}\r\n", status, reason(status));
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Content Too Large",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+// reads at most MAX_LINE bytes, so a peer that never sends a newline can't grow the line;
+// a line over that is a 431, anything else wrong with it a 400
+fn read_line(reader: &mut impl BufRead) -> Result<Option<String>, (u16, String)> {
+    let mut line = Vec::new();
+    let read = reader
+        .take(MAX_LINE as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .map_err(|e| (400, format!("failed to read: {}", e)))?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if line.len() > MAX_LINE {
+        return Err((431, format!("line is longer than {} bytes", MAX_LINE)));
+    }
+    let line = String::from_utf8(line).map_err(|_| (400, "line is not valid utf-8".to_string()))?;
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+// the start line and headers, with header names lowercased; None when the peer sent nothing.
+// A bad start line is a 400 and oversized headers a 431, for the server to answer with
+fn read_head(reader: &mut impl BufRead) -> Result<Option<(String, Headers)>, (u16, String)> {
+    let Some(start_line) = read_line(reader).map_err(|(_, message)| (400, message))? else {
+        return Ok(None);
+    };
+    let mut headers = Vec::new();
+    loop {
+        let line =
+            read_line(reader)?.ok_or((400, "connection closed inside the headers".to_string()))?;
+        if line.is_empty() {
+            return Ok(Some((start_line, headers)));
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err((431, format!("more than {} headers", MAX_HEADERS)));
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| (400, format!("malformed header '{}'", line)))?;
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+}
+
+// chunked or content-length framed; responses without either run to the end of the stream.
+// Nothing is allocated up front, so a lying length fails on `max_body` or a short read instead.
+fn read_body(
+    reader: &mut impl BufRead,
+    headers: &[(String, String)],
+    until_eof: bool,
+    max_body: usize,
+) -> Result<Vec<u8>, (u16, String)> {
+    let bad = |message: String| (400, message);
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let mut body = Vec::new();
+    if header("transfer-encoding").is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"))
+    {
+        loop {
+            let line = read_line(reader)?
+                .ok_or_else(|| bad("connection closed inside a chunk".to_string()))?;
+            let size = line.split(';').next().unwrap_or_default().trim();
+            let size = usize::from_str_radix(size, 16)
+                .map_err(|_| bad(format!("malformed chunk size '{}'", line)))?;
+            if size == 0 {
+                // trailers, up to the closing blank line
+                while read_line(reader)?.is_some_and(|line| !line.is_empty()) {}
+                return Ok(body);
+            }
+            read_exactly(reader, &mut body, size, max_body)?;
+            read_line(reader)?;
+        }
+    }
+    match header("content-length") {
+        Some(length) => {
+            let length: usize = length
+                .parse()
+                .map_err(|_| bad(format!("malformed content-length '{}'", length)))?;
+            read_exactly(reader, &mut body, length, max_body)?;
+        }
+        None if until_eof => {
+            // one byte past the limit is enough to know it was exceeded
+            reader
+                .take(max_body as u64 + 1)
+                .read_to_end(&mut body)
+                .map_err(|e| bad(format!("failed to read the body: {}", e)))?;
+            if body.len() > max_body {
+                return Err(too_large(max_body));
+            }
+        }
+        None => {}
+    }
+    Ok(body)
+}
+
+// appends `size` more bytes to `body`, as long as the total stays within `max_body`
+fn read_exactly(
+    reader: &mut impl BufRead,
+    body: &mut Vec<u8>,
+    size: usize,
+    max_body: usize,
+) -> Result<(), (u16, String)> {
+    body.len()
+        .checked_add(size)
+        .filter(|total| *total <= max_body)
+        .ok_or_else(|| too_large(max_body))?;
+    let read = reader
+        .take(size as u64)
+        .read_to_end(body)
+        .map_err(|e| (400, format!("failed to read the body: {}", e)))?;
+    if read < size {
+        return Err((400, "connection closed inside the body".to_string()));
+    }
+    Ok(())
+}
+
+fn too_large(max_body: usize) -> (u16, String) {
+    (413, format!("body is larger than {} bytes", max_body))
+}
+
+fn headers_value(headers: Headers) -> Value {
+    Value::new_map(
+        headers
+            .into_iter()
+            .map(|(name, value)| (name, Value::String(value)))
+            .collect(),
+    )
+}
+
+// text when it's valid utf-8, bytes otherwise
+fn body_value(body: Vec<u8>) -> Value {
+    match String::from_utf8(body) {
+        Ok(text) => Value::String(text),
+        Err(e) => Value::Bytes(Bytes::from(e.into_bytes())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::parse_url;
+    use crate::testing::{self, connect, free_port};
+
+    #[test]
+    fn client_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+        let server = thread::spawn(move || {
+            let mut seen = Vec::new();
+            for response in [
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nX-Test: yes\r\n\r\n4\r\nhell\r\n1\r\no\r\n0\r\n\r\n",
+                "HTTP/1.1 201 Created\r\nContent-Length: 2\r\n\r\nok",
+            ] {
+                let (stream, _) = listener.accept().expect("accept");
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("request line");
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                if request.starts_with("POST") {
+                    let mut body = [0; 4];
+                    reader.read_exact(&mut body).expect("request body");
+                    request.push_str(std::str::from_utf8(&body).expect("utf-8 body"));
+                }
+                seen.push(request);
+                reader
+                    .get_mut()
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+            seen
+        });
+        let interpreter = testing::run(&format!(
+            "
+            var got = http.get(\"http://127.0.0.1:{port}/hello?x=1\", {{\"Accept\": \"text/plain\"}});
+            var posted = http.post(\"http://127.0.0.1:{port}/items\", \"data\");
+        "
+        ))
+        .expect("source should run");
+        let seen = server.join().expect("server thread");
+        assert!(seen[0].starts_with("GET /hello?x=1 HTTP/1.1\r\n"));
+        assert!(seen[0].contains("Accept: text/plain\r\n"));
+        assert!(seen[1].contains("Content-Length: 4\r\n"));
+        assert!(seen[1].ends_with("\r\n\r\ndata"));
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(
+            render("got"),
+            r#"{"status": 200, "headers": {"transfer-encoding": "chunked", "x-test": "yes"}, "body": "hello"}"#
+        );
+        assert_eq!(
+            render("posted"),
+            r#"{"status": 201, "headers": {"content-length": "2"}, "body": "ok"}"#
+        );
+        assert!(parse_url("https://example.com").is_err());
+        assert!(parse_url("http://localhost/a\r\nX-Evil: 1").is_err());
+        for source in [
+            "http.get(\"http://127.0.0.1:1/\", {\"X-A\": \"1\r\nX-B: 2\"});",
+            "http.get(\"http://127.0.0.1:1/\", {\"X-A: 1\": \"2\"});",
+        ] {
+            let error = testing::run(source).expect_err("header should be refused");
+            assert!(format!("{:?}", error).contains("line break"), "{}", source);
+        }
+        assert_eq!(
+            parse_url("http://localhost:8080"),
+            Ok(("localhost".to_string(), 8080, "/".to_string()))
+        );
+    }
+
+    #[test]
+    fn serves_requests_to_a_handler() {
+        let port = free_port();
+        let client = thread::spawn(move || {
+            let long_header = format!("GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n", "a".repeat(9000));
+            let many_headers = format!("GET / HTTP/1.1\r\n{}\r\n", "X-Many: 1\r\n".repeat(101));
+            [
+                "GET /greet?name=reef HTTP/1.1\r\nHost: x\r\n\r\n",
+                "POST /missing HTTP/1.1\r\nContent-Length: 3\r\n\r\nabc",
+                "POST /big HTTP/1.1\r\nContent-Length: 999999999999999999\r\n\r\n",
+                "POST /big HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nab\r\nffffffffffffffff\r\n",
+                "POST /big HTTP/1.1\r\nContent-Length: 9\r\n\r\n123456789",
+                &long_header,
+                &many_headers,
+            ]
+            .map(|request| {
+                let mut stream = connect(port);
+                stream.write_all(request.as_bytes()).expect("write request");
+                let mut response = String::new();
+                stream.read_to_string(&mut response).expect("read response");
+                response
+            })
+        });
+        let interpreter = testing::run(&format!(
+            "
+            var seen = [];
+            fun handle(request) {{
+                seen.push(request[\"method\"] + \" \" + request[\"path\"] + \" \" + request[\"query\"] + \" \" + request[\"body\"]);
+                if (request[\"path\"] == \"/greet\") {{
+                    return \"hi\";
+                }}
+                return {{\"status\": 404, \"headers\": {{\"X-Reason\": \"gone\"}}, \"body\": \"nope\"}};
+            }}
+            http.serve({port}, handle, {{\"limit\": 7, \"maxBody\": 8}});
+        "
+        ))
+        .expect("source should run");
+        let responses = client.join().expect("client thread");
+        assert_eq!(
+            responses[0],
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: 2\r\nConnection: close\r\n\r\nhi"
+        );
+        assert!(responses[1].starts_with("HTTP/1.1 404 Not Found\r\nX-Reason: gone\r\n"));
+        assert!(responses[1].ends_with("\r\n\r\nnope"));
+        for response in &responses[2..5] {
+            assert!(response.starts_with("HTTP/1.1 413 Content Too Large\r\n"));
+        }
+        for response in &responses[5..] {
+            assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+        }
+        let globals = interpreter.globals.borrow();
+        let seen = interpreter.stringify(&globals.get("seen").expect("defined"));
+        assert_eq!(seen, r#"["GET /greet name=reef ", "POST /missing  abc"]"#);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    io::{self, BufRead, Read, Write},
+    rc::Rc,
+};
+
+use crate::{
+    Value, environment::EnvRef, error::ReefError, func::NativeFunction, stdlib::expect_at_most,
+};
+
+pub fn define_natives(globals: &EnvRef) {
+    let natives = [
+        // prints the optional prompt without a newline, then reads a line
+        NativeFunction::variadic("input", 0, |interpreter, args| {
+            expect_at_most(&args, 1, "input")?;
+            let prompt = args.first().map(|prompt| interpreter.stringify(prompt));
+            input(
+                prompt.as_deref(),
+                &mut io::stdin().lock(),
+                &mut io::stdout(),
+            )
+            .map_err(|e| io_error("input", e))
+        }),
+        NativeFunction::new("readLine", 0, |_interpreter, _args| {
+            read_line(&mut io::stdin().lock()).map_err(|e| io_error("readLine", e))
+        }),
+        NativeFunction::new("readAll", 0, |_interpreter, _args| {
+            read_all(&mut io::stdin()).map_err(|e| io_error("readAll", e))
+        }),
+        NativeFunction::new("write", 1, |interpreter, args| {
+            let text = interpreter.stringify(&args[0]);
+            write(&text, &mut io::stdout()).map_err(|e| io_error("write", e))?;
+            Ok(Value::Nil)
+        }),
+        NativeFunction::new("eprint", 1, |interpreter, args| {
+            eprintln!("{}", interpreter.stringify(&args[0]));
+            Ok(Value::Nil)
+        }),
+    ];
+    for native in natives {
+        let name = native.name.clone();
+        globals
+            .borrow_mut()
+            .define(name, Value::Callable(Rc::new(native)))
+            .expect("expect io function to be definable");
+    }
+}
+
+fn input(
+    prompt: Option<&str>,
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+) -> io::Result<Value> {
+    if let Some(prompt) = prompt {
+        write(prompt, writer)?;
+    }
+    read_line(reader)
+}
+
+fn read_all(reader: &mut impl Read) -> io::Result<Value> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    Ok(Value::String(text))
+}
+
+// flushed straight away, so text without a newline still shows up
+fn write(text: &str, writer: &mut impl Write) -> io::Result<()> {
+    writer.write_all(text.as_bytes())?;
+    writer.flush()
+}
+
+// the line without its terminator, or nil at end of input
+pub fn read_line(reader: &mut impl BufRead) -> io::Result<Value> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(Value::Nil);
+    }
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    Ok(Value::String(line))
+}
+
+fn io_error(function: &str, error: io::Error) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() failed: {}", function, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{input, read_all, read_line, write};
+    use crate::Value;
+
+    #[test]
+    fn read_line_strips_terminators_and_ends_with_nil() {
+        let mut input = Cursor::new("first\r\nsecond\nlast");
+        let mut lines = Vec::new();
+        while let Value::String(line) = read_line(&mut input).expect("in-memory read") {
+            lines.push(line);
+        }
+        assert_eq!(lines, ["first", "second", "last"]);
+        assert!(matches!(read_line(&mut input), Ok(Value::Nil)));
+    }
+
+    #[test]
+    fn input_prompts_then_reads_a_line() {
+        let (mut reader, mut writer) = (Cursor::new("ada\nrest\n"), Vec::new());
+        let name = input(Some("name? "), &mut reader, &mut writer).expect("in-memory io");
+        assert!(matches!(name, Value::String(line) if line == "ada"));
+        let rest = input(None, &mut reader, &mut writer).expect("in-memory io");
+        assert!(matches!(rest, Value::String(line) if line == "rest"));
+        assert!(matches!(
+            input(None, &mut reader, &mut writer),
+            Ok(Value::Nil)
+        ));
+        assert_eq!(writer, b"name? ");
+    }
+
+    #[test]
+    fn read_all_and_write_pass_text_through() {
+        let text = read_all(&mut Cursor::new("one\r\ntwo\n")).expect("in-memory read");
+        assert!(matches!(text, Value::String(text) if text == "one\r\ntwo\n"));
+        assert!(
+            matches!(read_all(&mut Cursor::new("")), Ok(Value::String(text)) if text.is_empty())
+        );
+        assert!(read_all(&mut Cursor::new(b"\xff")).is_err());
+        let mut writer = Vec::new();
+        write("partial", &mut writer).expect("in-memory write");
+        write(" line\n", &mut writer).expect("in-memory write");
+        assert_eq!(writer, b"partial line\n");
+    }
+}
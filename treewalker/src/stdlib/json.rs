@@ -0,0 +1,474 @@
+use std::rc::Rc;
+
+use crate::{
+    Value,
+    bigint::BigInt,
+    error::ReefError,
+    func::NativeFunction,
+    map::ReefMap,
+    module::ReefModule,
+    number,
+    stdlib::{expect_at_most, int_arg, string_arg},
+};
+
+// deeper nesting is refused so hostile input can't overflow the parser's stack
+const MAX_DEPTH: usize = 512;
+
+pub fn module() -> ReefModule {
+    let json = ReefModule::new("json");
+    json.define_native(NativeFunction::new("parse", 1, |_interpreter, args| {
+        parse(string_arg(&args, 0, "json.parse")?)
+    }));
+    // `indent` is optional; without it the output is compact
+    json.define_native(NativeFunction::variadic(
+        "stringify",
+        1,
+        |_interpreter, args| {
+            expect_at_most(&args, 2, "json.stringify")?;
+            let indent = match args.get(1) {
+                None | Some(Value::Nil) => None,
+                Some(_) => {
+                    let indent = int_arg(&args, 1, "json.stringify")?;
+                    Some(usize::try_from(indent).map_err(|_| {
+                        ReefError::reef_native_error("json.stringify() indent must not be negative")
+                    })?)
+                }
+            };
+            stringify(&args[0], indent).map(Value::String)
+        },
+    ));
+    json
+}
+
+pub fn parse(text: &str) -> Result<Value, ReefError> {
+    let mut parser = JsonParser {
+        chars: text.chars().collect(),
+        current: 0,
+    };
+    parser.skip_whitespace();
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.current < parser.chars.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+pub fn stringify(value: &Value, indent: Option<usize>) -> Result<String, ReefError> {
+    let mut writer = JsonWriter {
+        out: String::new(),
+        indent,
+        seen: Vec::new(),
+    };
+    writer.value(value, 0)?;
+    Ok(writer.out)
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    current: usize,
+}
+
+impl JsonParser {
+    fn error(&self, message: &str) -> ReefError {
+        ReefError::reef_native_error(&format!(
+            "json.parse() {} at offset {}",
+            message, self.current
+        ))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.current).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.current += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ReefError> {
+        if self.peek() == Some(expected) {
+            self.current += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Value) -> Result<Value, ReefError> {
+        for expected in word.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ReefError> {
+        match self.peek() {
+            Some('{' | '[') if depth >= MAX_DEPTH => {
+                Err(self.error(&format!("nests deeper than {} levels", MAX_DEPTH)))
+            }
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('t') => self.keyword("true", Value::Boolean(true)),
+            Some('f') => self.keyword("false", Value::Boolean(false)),
+            Some('n') => self.keyword("null", Value::Nil),
+            Some('-' | '0'..='9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, ReefError> {
+        self.expect('{')?;
+        let mut map = ReefMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.current += 1;
+            return Ok(Value::new_map(map));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected string key"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+            map.insert(key, self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.current += 1,
+                Some('}') => {
+                    self.current += 1;
+                    return Ok(Value::new_map(map));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, ReefError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.current += 1;
+            return Ok(Value::new_list(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.current += 1,
+                Some(']') => {
+                    self.current += 1;
+                    return Ok(Value::new_list(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ReefError> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some('"') => {
+                    self.current += 1;
+                    return Ok(text);
+                }
+                Some('\\') => {
+                    self.current += 1;
+                    let escaped = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            self.current += 1;
+                            text.push(self.unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    self.current += 1;
+                    text.push(escaped);
+                }
+                Some(c) if c < ' ' => return Err(self.error("control character in string")),
+                Some(c) => {
+                    self.current += 1;
+                    text.push(c);
+                }
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, ReefError> {
+        let digits: String = self.chars.iter().skip(self.current).take(4).collect();
+        if digits.chars().count() != 4 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(self.error("invalid unicode escape"));
+        }
+        self.current += 4;
+        Ok(u32::from_str_radix(&digits, 16).expect("checked hex digits"))
+    }
+
+    // called after `\u`; combines utf-16 surrogate pairs into one char
+    fn unicode_escape(&mut self) -> Result<char, ReefError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            self.expect('\\')?;
+            self.expect('u')?;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("invalid surrogate pair"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    // integers stay exact (promoting to bigints); anything with a fraction or exponent is a float
+    fn number(&mut self) -> Result<Value, ReefError> {
+        let start = self.current;
+        if self.peek() == Some('-') {
+            self.current += 1;
+        }
+        match self.peek() {
+            Some('0') => self.current += 1,
+            Some('1'..='9') => self.digits(),
+            _ => return Err(self.error("expected digits")),
+        }
+        let mut is_float = false;
+        if self.peek() == Some('.') {
+            is_float = true;
+            self.current += 1;
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.error("expected digits after '.'"));
+            }
+            self.digits();
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            is_float = true;
+            self.current += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.current += 1;
+            }
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.error("expected digits in exponent"));
+            }
+            self.digits();
+        }
+        let text: String = self.chars[start..self.current].iter().collect();
+        if is_float {
+            return text
+                .parse::<f64>()
+                .map(Value::Number)
+                .map_err(|_| self.error("invalid number"));
+        }
+        match text.parse::<i64>() {
+            Ok(n) => Ok(Value::Int(n)),
+            Err(_) => Ok(number::from_bigint(
+                BigInt::parse_radix(&text, 10).expect("scanned decimal digits"),
+            )),
+        }
+    }
+
+    fn digits(&mut self) {
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.current += 1;
+        }
+    }
+}
+
+struct JsonWriter {
+    out: String,
+    indent: Option<usize>,
+    // containers currently being written, to reject cycles
+    seen: Vec<*const ()>,
+}
+
+impl JsonWriter {
+    fn value(&mut self, value: &Value, depth: usize) -> Result<(), ReefError> {
+        match value {
+            Value::Nil => self.out.push_str("null"),
+            Value::Boolean(b) => self.out.push_str(&b.to_string()),
+            Value::Int(n) => self.out.push_str(&n.to_string()),
+            Value::BigInt(n) => self.out.push_str(&n.to_string()),
+            Value::Number(n) if n.is_finite() => self.out.push_str(&format!("{:?}", n)),
+            Value::String(s) => self.string(s),
+            Value::List(items) => {
+                self.enter(Rc::as_ptr(items) as *const ())?;
+                let items = items.borrow().clone();
+                self.container('[', ']', depth, items.iter(), |writer, item| {
+                    writer.value(item, depth + 1)
+                })?;
+                self.seen.pop();
+            }
+            // tuples can only reach themselves through a list or map, which is tracked
+            Value::Tuple(items) => {
+                self.container('[', ']', depth, items.iter(), |writer, item| {
+                    writer.value(item, depth + 1)
+                })?;
+            }
+            Value::Map(entries) => {
+                self.enter(Rc::as_ptr(entries) as *const ())?;
+                let entries = entries
+                    .borrow()
+                    .iter()
+                    .map(|(key, item)| match key {
+                        Value::String(key) => Ok((key.clone(), item.clone())),
+                        other => Err(ReefError::reef_native_error(&format!(
+                            "json.stringify() object keys must be strings, got {}",
+                            other.type_name()
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.object(&entries, depth)?;
+                self.seen.pop();
+            }
+            Value::Instance(instance) => {
+                self.enter(Rc::as_ptr(instance) as *const ())?;
+                self.object(&instance.fields(), depth)?;
+                self.seen.pop();
+            }
+            _ => {
+                return Err(ReefError::reef_native_error(&format!(
+                    "json.stringify() cannot serialize a {}",
+                    value.type_name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn enter(&mut self, pointer: *const ()) -> Result<(), ReefError> {
+        if self.seen.contains(&pointer) {
+            return Err(ReefError::reef_native_error(
+                "json.stringify() cannot serialize a cyclic structure",
+            ));
+        }
+        self.seen.push(pointer);
+        Ok(())
+    }
+
+    fn object(&mut self, entries: &[(String, Value)], depth: usize) -> Result<(), ReefError> {
+        let separator = if self.indent.is_some() { ": " } else { ":" };
+        self.container('{', '}', depth, entries.iter(), |writer, (key, item)| {
+            writer.string(key);
+            writer.out.push_str(separator);
+            writer.value(item, depth + 1)
+        })
+    }
+
+    fn container<'a, T: 'a>(
+        &mut self,
+        open: char,
+        close: char,
+        depth: usize,
+        items: impl ExactSizeIterator<Item = &'a T>,
+        mut write_item: impl FnMut(&mut Self, &'a T) -> Result<(), ReefError>,
+    ) -> Result<(), ReefError> {
+        self.out.push(open);
+        let empty = items.len() == 0;
+        for (i, item) in items.enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.newline(depth + 1);
+            write_item(self, item)?;
+        }
+        if !empty {
+            self.newline(depth);
+        }
+        self.out.push(close);
+        Ok(())
+    }
+
+    fn newline(&mut self, depth: usize) {
+        if let Some(indent) = self.indent {
+            self.out.push('\n');
+            self.out.push_str(&" ".repeat(indent * depth));
+        }
+    }
+
+    fn string(&mut self, s: &str) {
+        self.out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                c if c < ' ' => self.out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, stringify};
+    use crate::{Value, testing};
+
+    #[test]
+    fn parse_and_stringify_round_trip() {
+        let data = parse(
+            r#"{"name": "reef", "tags": [1, 2.5, true, null], "big": 18446744073709551616,
+                "esc": "a\n\u00e9\ud83d\ude00"}"#,
+        )
+        .expect("valid json");
+        assert_eq!(
+            stringify(&data, None).expect("serializable"),
+            r#"{"name":"reef","tags":[1,2.5,true,null],"big":18446744073709551616,"esc":"a\né😀"}"#
+        );
+        let interpreter = testing::run(
+            "
+            var pretty = json.stringify({\"a\": [1, {}], \"b\": []}, 2);
+            class Point { init(x, y) { this.y = y; this.x = x; } }
+            var point = json.stringify(Point(1, 2.0));
+            var back = json.parse(point)[\"y\"];
+            ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(
+            render("pretty"),
+            "{\n  \"a\": [\n    1,\n    {}\n  ],\n  \"b\": []\n}"
+        );
+        assert_eq!(render("point"), r#"{"x":1,"y":2.0}"#);
+        assert!(matches!(globals.get("back"), Ok(Value::Number(n)) if n == 2.0));
+    }
+
+    #[test]
+    fn rejects_bad_input_and_unserializable_values() {
+        for text in ["[1, 2", r#"{"a" 1}"#, "01", r#""\x""#, "[1,]"] {
+            assert!(parse(text).is_err(), "{} should not parse", text);
+        }
+        assert!(parse(&"[".repeat(200_000)).is_err());
+        assert!(parse(&format!("{}{}", "[".repeat(100), "]".repeat(100))).is_ok());
+        for source in [
+            "json.stringify(clock);",
+            "var l = [1]; l.push(l); json.stringify(l);",
+            "json.stringify(math.NAN);",
+            "json.stringify(rational(1, 3));",
+        ] {
+            assert!(testing::run(source).is_err(), "{} should fail", source);
+        }
+    }
+}
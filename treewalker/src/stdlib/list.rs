@@ -0,0 +1,29 @@
+use crate::{Value, error::ReefError, expr::ListRef, func::NativeFunction};
+
+// methods are bound to the receiving list, which arrives as `args[0]`
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "len" => NativeFunction::new("len", 0, |_interpreter, args| {
+            Ok(Value::Int(receiver(&args).borrow().len() as i64))
+        }),
+        "push" => NativeFunction::new("push", 1, |_interpreter, args| {
+            receiver(&args).borrow_mut().push(args[1].clone());
+            Ok(Value::Nil)
+        }),
+        "pop" => NativeFunction::new("pop", 0, |_interpreter, args| {
+            receiver(&args)
+                .borrow_mut()
+                .pop()
+                .ok_or_else(|| ReefError::reef_native_error("pop() from an empty list"))
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn receiver(args: &[Value]) -> &ListRef {
+    match &args[0] {
+        Value::List(items) => items,
+        _ => unreachable!("list methods are only bound to lists"),
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{Value, error::ReefError, expr::MapRef, func::NativeFunction, stdlib::expect_at_most};
+
+// methods are bound to the receiving map, which arrives as `args[0]`
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "len" => NativeFunction::new("len", 0, |_interpreter, args| {
+            Ok(Value::Int(receiver(&args).borrow().len() as i64))
+        }),
+        "keys" => NativeFunction::new("keys", 0, |_interpreter, args| {
+            let keys = receiver(&args)
+                .borrow()
+                .iter()
+                .map(|(key, _)| key.clone())
+                .collect();
+            Ok(Value::new_list(keys))
+        }),
+        "values" => NativeFunction::new("values", 0, |_interpreter, args| {
+            let values = receiver(&args)
+                .borrow()
+                .iter()
+                .map(|(_, value)| value.clone())
+                .collect();
+            Ok(Value::new_list(values))
+        }),
+        "has" => NativeFunction::new("has", 1, |_interpreter, args| {
+            let entries = receiver(&args).borrow();
+            let found = entries.get_value(&args[1]).map_err(key_error)?;
+            Ok(Value::Boolean(found.is_some()))
+        }),
+        // like indexing, but falls back to the default (or nil) for missing keys
+        "get" => NativeFunction::variadic("get", 1, |_interpreter, args| {
+            expect_at_most(&args[1..], 2, "get")?;
+            let fallback = args.get(2).cloned().unwrap_or(Value::Nil);
+            let entries = receiver(&args).borrow();
+            let found = entries.get_value(&args[1]).map_err(key_error)?;
+            Ok(found.cloned().unwrap_or(fallback))
+        }),
+        "remove" => NativeFunction::new("remove", 1, |interpreter, args| {
+            receiver(&args)
+                .borrow_mut()
+                .remove_value(&args[1])
+                .map_err(key_error)?
+                .ok_or_else(|| {
+                    ReefError::reef_native_error(&format!(
+                        "undefined key {}",
+                        interpreter.repr(&args[1])
+                    ))
+                })
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn key_error(message: String) -> ReefError {
+    ReefError::reef_native_error(&message)
+}
+
+fn receiver(args: &[Value]) -> &MapRef {
+    match &args[0] {
+        Value::Map(entries) => entries,
+        _ => unreachable!("map methods are only bound to maps"),
+    }
+}
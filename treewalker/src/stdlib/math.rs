@@ -0,0 +1,285 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{
+    Value, bigint::BigInt, error::ReefError, func::NativeFunction, module::ReefModule, number,
+    rational::Rational, stdlib::number_arg,
+};
+
+macro_rules! float_fn {
+    ($module:expr, $name:literal, $f:path) => {
+        $module.define_native(NativeFunction::new($name, 1, |_interpreter, args| {
+            Ok(Value::Number($f(number_arg(
+                &args,
+                0,
+                concat!("math.", $name),
+            )?)))
+        }))
+    };
+}
+
+// exact powers with more bits than this are refused rather than computed for minutes
+const MAX_BITS: u64 = 1 << 20;
+
+pub fn module() -> ReefModule {
+    let math = ReefModule::new("math");
+    math.define("PI", Value::Number(std::f64::consts::PI));
+    math.define("E", Value::Number(std::f64::consts::E));
+    math.define("INF", Value::Number(f64::INFINITY));
+    math.define("NAN", Value::Number(f64::NAN));
+
+    float_fn!(math, "sqrt", f64::sqrt);
+    float_fn!(math, "sin", f64::sin);
+    float_fn!(math, "cos", f64::cos);
+    float_fn!(math, "tan", f64::tan);
+    float_fn!(math, "asin", f64::asin);
+    float_fn!(math, "acos", f64::acos);
+    float_fn!(math, "atan", f64::atan);
+    float_fn!(math, "sinh", f64::sinh);
+    float_fn!(math, "cosh", f64::cosh);
+    float_fn!(math, "tanh", f64::tanh);
+    float_fn!(math, "asinh", f64::asinh);
+    float_fn!(math, "acosh", f64::acosh);
+    float_fn!(math, "atanh", f64::atanh);
+    float_fn!(math, "log", f64::ln);
+    float_fn!(math, "log2", f64::log2);
+    float_fn!(math, "log10", f64::log10);
+    float_fn!(math, "exp", f64::exp);
+
+    math.define_native(NativeFunction::new("atan2", 2, |_interpreter, args| {
+        let y = number_arg(&args, 0, "math.atan2")?;
+        let x = number_arg(&args, 1, "math.atan2")?;
+        Ok(Value::Number(y.atan2(x)))
+    }));
+    math.define_native(NativeFunction::new("hypot", 2, |_interpreter, args| {
+        let x = number_arg(&args, 0, "math.hypot")?;
+        let y = number_arg(&args, 1, "math.hypot")?;
+        Ok(Value::Number(x.hypot(y)))
+    }));
+    math.define_native(NativeFunction::new("pow", 2, |_interpreter, args| {
+        match (&args[0], &args[1]) {
+            (Value::Int(_) | Value::BigInt(_) | Value::Rational(_), Value::Int(exponent))
+                if *exponent >= 0 =>
+            {
+                exact_pow(&args[0], *exponent as u64)
+            }
+            _ => {
+                let base = number_arg(&args, 0, "math.pow")?;
+                let exponent = number_arg(&args, 1, "math.pow")?;
+                Ok(Value::Number(base.powf(exponent)))
+            }
+        }
+    }));
+    math.define_native(NativeFunction::new("abs", 1, |_interpreter, args| {
+        number_arg(&args, 0, "math.abs")?;
+        if number::compare(&args[0], &Value::Int(0)) == Some(Some(Ordering::Less)) {
+            Ok(number::negate(&args[0]).expect("checked numeric"))
+        } else {
+            Ok(args[0].clone())
+        }
+    }));
+    math.define_native(NativeFunction::new("floor", 1, |_interpreter, args| {
+        round_with(&args[0], "math.floor", f64::floor, rational_floor)
+    }));
+    math.define_native(NativeFunction::new("ceil", 1, |_interpreter, args| {
+        round_with(&args[0], "math.ceil", f64::ceil, |r| -&rational_floor(&-r))
+    }));
+    math.define_native(NativeFunction::new("trunc", 1, |_interpreter, args| {
+        round_with(&args[0], "math.trunc", f64::trunc, |r| {
+            r.numerator()
+                .div_rem(r.denominator())
+                .expect("denominator is non-zero")
+                .0
+        })
+    }));
+    math.define_native(NativeFunction::new("round", 1, |_interpreter, args| {
+        // halves round away from zero, matching f64::round
+        round_with(&args[0], "math.round", f64::round, |r| {
+            let half = Rational::new(BigInt::one(), BigInt::from_i64(2)).expect("non-zero");
+            if r.numerator().is_negative() {
+                -&rational_floor(&(&-r + &half))
+            } else {
+                rational_floor(&(r + &half))
+            }
+        })
+    }));
+    math.define_native(NativeFunction::variadic("min", 1, |_interpreter, args| {
+        extreme(&args, "math.min", Ordering::Less)
+    }));
+    math.define_native(NativeFunction::variadic("max", 1, |_interpreter, args| {
+        extreme(&args, "math.max", Ordering::Greater)
+    }));
+    math.define_native(NativeFunction::new("clamp", 3, |_interpreter, args| {
+        for index in 0..3 {
+            number_arg(&args, index, "math.clamp")?;
+        }
+        let (value, low, high) = (&args[0], &args[1], &args[2]);
+        if number::compare(low, high) == Some(Some(Ordering::Greater)) {
+            return Err(ReefError::reef_native_error(
+                "math.clamp() lower bound is greater than upper bound",
+            ));
+        }
+        if number::compare(value, low) == Some(Some(Ordering::Less)) {
+            Ok(low.clone())
+        } else if number::compare(value, high) == Some(Some(Ordering::Greater)) {
+            Ok(high.clone())
+        } else {
+            Ok(value.clone())
+        }
+    }));
+    math.define_native(NativeFunction::new("isNaN", 1, |_interpreter, args| {
+        Ok(Value::Boolean(number_arg(&args, 0, "math.isNaN")?.is_nan()))
+    }));
+    math.define_native(NativeFunction::new("isFinite", 1, |_interpreter, args| {
+        let finite = match &args[0] {
+            Value::Number(n) => n.is_finite(),
+            _ => number_arg(&args, 0, "math.isFinite").map(|_| true)?,
+        };
+        Ok(Value::Boolean(finite))
+    }));
+    math
+}
+
+fn exact_pow(base: &Value, exponent: u64) -> Result<Value, ReefError> {
+    // a base of `bits` bits raised to `exponent` has at least (bits - 1) * exponent + 1 bits
+    let bits = match base {
+        Value::Rational(r) => r.numerator().bits().max(r.denominator().bits()),
+        _ => number::to_bigint(base).map_or(0, |n| n.bits()),
+    };
+    if bits.saturating_sub(1).saturating_mul(exponent) >= MAX_BITS {
+        return Err(ReefError::reef_native_error(&format!(
+            "math.pow() result would be larger than {} bits",
+            MAX_BITS
+        )));
+    }
+    match base {
+        _ if exponent == 0 => Ok(Value::Int(1)),
+        // powers of coprime parts stay coprime, which spares reducing a huge fraction
+        Value::Rational(r) => Ok(Value::Rational(Rc::new(Rational::from_lowest_terms(
+            bigint_pow(r.numerator().clone(), exponent),
+            bigint_pow(r.denominator().clone(), exponent),
+        )))),
+        _ => {
+            let base = number::to_bigint(base).expect("pow() only passes exact numbers");
+            Ok(number::from_bigint(bigint_pow(base, exponent)))
+        }
+    }
+}
+
+fn bigint_pow(mut base: BigInt, mut exponent: u64) -> BigInt {
+    let mut result = BigInt::one();
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = &result * &base;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = &base * &base;
+        }
+    }
+    result
+}
+
+fn rational_floor(r: &Rational) -> BigInt {
+    let (quotient, remainder) = r
+        .numerator()
+        .div_rem(r.denominator())
+        .expect("denominator is non-zero");
+    if remainder.is_negative() {
+        &quotient - &BigInt::one()
+    } else {
+        quotient
+    }
+}
+
+// integers pass through; floats and rationals round to an integer value
+fn round_with(
+    value: &Value,
+    function: &str,
+    float_op: fn(f64) -> f64,
+    rational_op: fn(&Rational) -> BigInt,
+) -> Result<Value, ReefError> {
+    match value {
+        Value::Int(_) | Value::BigInt(_) => Ok(value.clone()),
+        Value::Rational(r) => Ok(number::from_bigint(rational_op(r))),
+        Value::Number(n) if n.is_finite() => {
+            let rounded = float_op(*n);
+            let digits = format!("{:.0}", rounded);
+            Ok(number::from_bigint(
+                BigInt::parse_radix(&digits, 10).expect("integral float formats as digits"),
+            ))
+        }
+        Value::Number(n) => Err(ReefError::reef_native_error(&format!(
+            "{}() cannot convert {} to an integer",
+            function, n
+        ))),
+        _ => number_arg(std::slice::from_ref(value), 0, function).map(|_| Value::Nil),
+    }
+}
+
+fn extreme(args: &[Value], function: &str, keep: Ordering) -> Result<Value, ReefError> {
+    for index in 0..args.len() {
+        number_arg(args, index, function)?;
+    }
+    let mut best = &args[0];
+    for arg in &args[1..] {
+        if number::compare(arg, best) == Some(Some(keep)) {
+            best = arg;
+        }
+    }
+    Ok(best.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Value, testing};
+
+    #[test]
+    fn math_functions_keep_exact_types() {
+        let interpreter = testing::run(
+            "
+            var root = math.sqrt(16);
+            var power = math.pow(2, 100);
+            var one = math.pow(-1, 1000000000001);
+            var cube = math.pow(rational(-2, 3), 3);
+            var floored = math.floor(-2.5);
+            var rounded = math.round(rational(5, 2));
+            var smallest = math.min(3, 1.5, 2);
+            var clamped = math.clamp(15, 0, 10);
+            var absolute = math.abs(-7);
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("root"), "4.0");
+        assert_eq!(render("power"), "1267650600228229401496703205376");
+        assert!(matches!(globals.get("one"), Ok(Value::Int(-1))));
+        assert_eq!(render("cube"), "-8/27");
+        assert!(matches!(globals.get("floored"), Ok(Value::Int(-3))));
+        assert_eq!(render("rounded"), "3");
+        assert_eq!(render("smallest"), "1.5");
+        assert_eq!(render("clamped"), "10");
+        assert!(matches!(globals.get("absolute"), Ok(Value::Int(7))));
+    }
+
+    #[test]
+    fn math_type_errors_are_runtime_errors() {
+        for source in [
+            "math.sqrt(\"four\");",
+            "math.max();",
+            "math.floor(math.NAN);",
+            "math.clamp(1, 10, 0);",
+            "math.pow(2, 1000000000000);",
+            "math.pow(rational(1, 3), 10000000);",
+        ] {
+            assert!(
+                matches!(
+                    testing::run(source),
+                    Err(crate::error::ReefError::RuntimeError(_))
+                ),
+                "{} should fail at runtime",
+                source
+            );
+        }
+    }
+}
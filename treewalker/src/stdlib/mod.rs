@@ -0,0 +1,159 @@
+use std::rc::Rc;
+
+use ::bytes::Bytes;
+
+use crate::{
+    Value, environment::EnvRef, error::ReefError, expr::ListRef, func::ReefCallable,
+    interpreter::Interpreter,
+};
+
+pub mod bytes;
+pub mod collections;
+pub mod csv;
+pub mod encoding;
+pub mod format;
+pub mod fs;
+pub mod hash;
+pub mod http;
+pub mod io;
+pub mod json;
+pub mod list;
+pub mod map;
+pub mod math;
+pub mod net;
+pub mod proc;
+pub mod random;
+pub mod re;
+pub mod reflect;
+pub mod set;
+pub mod string;
+pub mod sys;
+pub mod time;
+pub mod tuple;
+
+pub fn define_modules(globals: &EnvRef, args: Vec<String>) {
+    let modules = [
+        bytes::module(),
+        csv::module(),
+        encoding::module(),
+        fs::module(),
+        hash::module(),
+        http::module(),
+        json::module(),
+        math::module(),
+        net::module(),
+        proc::module(),
+        random::module(),
+        re::module(),
+        sys::module(args),
+        time::module(),
+    ];
+    for module in modules {
+        globals
+            .borrow_mut()
+            .define(module.name.clone(), Value::Module(Rc::new(module)))
+            .expect("expect module to be definable");
+    }
+}
+
+pub fn number_arg(args: &[Value], index: usize, function: &str) -> Result<f64, ReefError> {
+    args[index]
+        .as_float()
+        .ok_or_else(|| type_error(function, index, "a number", &args[index]))
+}
+
+pub fn int_arg(args: &[Value], index: usize, function: &str) -> Result<i64, ReefError> {
+    match &args[index] {
+        Value::Int(n) => Ok(*n),
+        other => Err(type_error(function, index, "an integer", other)),
+    }
+}
+
+pub fn string_arg<'a>(
+    args: &'a [Value],
+    index: usize,
+    function: &str,
+) -> Result<&'a str, ReefError> {
+    match &args[index] {
+        Value::String(s) => Ok(s),
+        other => Err(type_error(function, index, "a string", other)),
+    }
+}
+
+pub fn list_arg(args: &[Value], index: usize, function: &str) -> Result<ListRef, ReefError> {
+    match &args[index] {
+        Value::List(items) => Ok(Rc::clone(items)),
+        other => Err(type_error(function, index, "a list", other)),
+    }
+}
+
+pub fn bytes_arg(args: &[Value], index: usize, function: &str) -> Result<Bytes, ReefError> {
+    match &args[index] {
+        Value::Bytes(buf) => Ok(buf.clone()),
+        other => Err(type_error(function, index, "bytes", other)),
+    }
+}
+
+// strings are taken as their utf-8 bytes
+pub fn data_arg(args: &[Value], index: usize, function: &str) -> Result<Bytes, ReefError> {
+    match &args[index] {
+        Value::Bytes(buf) => Ok(buf.clone()),
+        Value::String(s) => Ok(Bytes::copy_from_slice(s.as_bytes())),
+        other => Err(type_error(function, index, "a string or bytes", other)),
+    }
+}
+
+pub fn type_error(function: &str, index: usize, expected: &str, got: &Value) -> ReefError {
+    ReefError::reef_native_error(&format!(
+        "{}() expects {} for argument {}, got {}",
+        function,
+        expected,
+        index + 1,
+        got.type_name()
+    ))
+}
+
+// checks the callback's arity the way a direct call would; its own errors pass through untouched
+pub fn call(
+    interpreter: &mut Interpreter,
+    callable: &Rc<dyn ReefCallable>,
+    args: Vec<Value>,
+    function: &str,
+) -> Result<Value, ReefError> {
+    let arity = callable.arity();
+    let fits = match callable.is_variadic() {
+        true => args.len() >= arity,
+        false => args.len() == arity,
+    };
+    if !fits {
+        return Err(ReefError::reef_native_error(&format!(
+            "{}() passes {} args to {}, which expects {}",
+            function,
+            args.len(),
+            callable.name(),
+            arity
+        )));
+    }
+    callable.call(interpreter, args)
+}
+
+// variadic natives take optional trailing args, but no more than they use
+pub fn expect_at_most(args: &[Value], max: usize, function: &str) -> Result<(), ReefError> {
+    if args.len() > max {
+        return Err(ReefError::reef_native_error(&format!(
+            "{}() expects at most {} args, got {}",
+            function,
+            max,
+            args.len()
+        )));
+    }
+    Ok(())
+}
+
+// resolves a possibly negative index against `len`, counting negatives from the end
+pub fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let position = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64)
+        .contains(&position)
+        .then_some(position as usize)
+}
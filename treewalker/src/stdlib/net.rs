@@ -0,0 +1,248 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    io::{self, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    rc::Rc,
+};
+
+use bytes::Bytes;
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    module::ReefModule,
+    stdlib::{expect_at_most, int_arg, io::read_line, string_arg, sys::env_read_lock, type_error},
+};
+
+pub type SocketRef = Rc<RefCell<Socket>>;
+
+const MAX_READ: usize = 64 * 1024;
+
+// a listening or connected TCP socket; closing drops the OS handle but keeps the value around
+#[derive(Debug)]
+pub enum Socket {
+    Listener(TcpListener),
+    Stream(BufReader<TcpStream>),
+    Closed,
+}
+
+impl fmt::Display for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Socket::Listener(listener) => match listener.local_addr() {
+                Ok(addr) => write!(f, "<listener {}>", addr),
+                Err(_) => write!(f, "<listener>"),
+            },
+            Socket::Stream(stream) => match stream.get_ref().peer_addr() {
+                Ok(addr) => write!(f, "<socket {}>", addr),
+                Err(_) => write!(f, "<socket>"),
+            },
+            Socket::Closed => write!(f, "<closed socket>"),
+        }
+    }
+}
+
+pub fn module() -> ReefModule {
+    let net = ReefModule::new("net");
+    // listens on localhost; port 0 picks a free port, which `port()` reports
+    net.define_native(NativeFunction::new("listen", 1, |_interpreter, args| {
+        let port = port_arg(&args, 0, "net.listen")?;
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| net_error("net.listen", &format!("port {}", port), e))?;
+        Ok(socket_value(Socket::Listener(listener)))
+    }));
+    net.define_native(NativeFunction::new("connect", 2, |_interpreter, args| {
+        let host = string_arg(&args, 0, "net.connect")?;
+        let port = port_arg(&args, 1, "net.connect")?;
+        // resolving a host name reads resolver settings from the environment
+        let stream = {
+            let _env = env_read_lock();
+            TcpStream::connect((host, port))
+        }
+        .map_err(|e| net_error("net.connect", &format!("{}:{}", host, port), e))?;
+        Ok(socket_value(Socket::Stream(BufReader::new(stream))))
+    }));
+    net
+}
+
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "accept" => NativeFunction::new("accept", 0, |_interpreter, args| {
+            let socket = socket_arg(&args);
+            let socket = socket.borrow();
+            let Socket::Listener(listener) = &*socket else {
+                return Err(wrong_kind("accept", &socket));
+            };
+            let (stream, _) = listener
+                .accept()
+                .map_err(|e| net_error("accept", "listener", e))?;
+            Ok(socket_value(Socket::Stream(BufReader::new(stream))))
+        }),
+        "port" => NativeFunction::new("port", 0, |_interpreter, args| {
+            let socket = socket_arg(&args);
+            let socket = socket.borrow();
+            let addr = match &*socket {
+                Socket::Listener(listener) => listener.local_addr(),
+                Socket::Stream(stream) => stream.get_ref().local_addr(),
+                Socket::Closed => return Err(wrong_kind("port", &socket)),
+            };
+            let addr = addr.map_err(|e| net_error("port", "socket", e))?;
+            Ok(Value::Int(addr.port() as i64))
+        }),
+        // read([max]) returns up to `max` bytes as soon as any arrive, or nil at end of stream
+        "read" => NativeFunction::variadic("read", 0, |_interpreter, args| {
+            expect_at_most(&args[1..], 1, "read")?;
+            let max = match args.get(1) {
+                Some(_) => int_arg(&args[1..], 0, "read")?,
+                None => 4096,
+            };
+            let max = usize::try_from(max)
+                .ok()
+                .filter(|max| *max > 0)
+                .ok_or_else(|| ReefError::reef_native_error("read() size must be positive"))?;
+            let socket = socket_arg(&args);
+            let mut socket = socket.borrow_mut();
+            let stream = stream(&mut socket, "read")?;
+            // a read returns whatever has arrived, so a huge `max` needn't be allocated up front
+            let mut buf = vec![0; max.min(MAX_READ)];
+            let read = stream
+                .read(&mut buf)
+                .map_err(|e| net_error("read", "socket", e))?;
+            if read == 0 {
+                return Ok(Value::Nil);
+            }
+            buf.truncate(read);
+            Ok(Value::Bytes(Bytes::from(buf)))
+        }),
+        "readLine" => NativeFunction::new("readLine", 0, |_interpreter, args| {
+            let socket = socket_arg(&args);
+            let mut socket = socket.borrow_mut();
+            read_line(stream(&mut socket, "readLine")?)
+                .map_err(|e| net_error("readLine", "socket", e))
+        }),
+        // strings are sent as utf-8
+        "write" => NativeFunction::new("write", 1, |_interpreter, args| {
+            let data = match &args[1] {
+                Value::String(s) => Bytes::copy_from_slice(s.as_bytes()),
+                Value::Bytes(buf) => buf.clone(),
+                other => return Err(type_error("write", 0, "a string or bytes", other)),
+            };
+            let socket = socket_arg(&args);
+            let mut socket = socket.borrow_mut();
+            let stream = stream(&mut socket, "write")?.get_mut();
+            stream
+                .write_all(&data)
+                .and_then(|_| stream.flush())
+                .map_err(|e| net_error("write", "socket", e))?;
+            Ok(Value::Nil)
+        }),
+        "close" => NativeFunction::new("close", 0, |_interpreter, args| {
+            *socket_arg(&args).borrow_mut() = Socket::Closed;
+            Ok(Value::Nil)
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn socket_value(socket: Socket) -> Value {
+    Value::Socket(Rc::new(RefCell::new(socket)))
+}
+
+fn socket_arg(args: &[Value]) -> SocketRef {
+    match &args[0] {
+        Value::Socket(socket) => Rc::clone(socket),
+        _ => unreachable!("socket methods are only bound to sockets"),
+    }
+}
+
+fn stream<'a>(
+    socket: &'a mut Socket,
+    function: &str,
+) -> Result<&'a mut BufReader<TcpStream>, ReefError> {
+    match socket {
+        Socket::Stream(stream) => Ok(stream),
+        other => Err(wrong_kind(function, other)),
+    }
+}
+
+fn wrong_kind(function: &str, socket: &Socket) -> ReefError {
+    let kind = match socket {
+        Socket::Listener(_) => "a listener",
+        Socket::Stream(_) => "a connection",
+        Socket::Closed => "a closed socket",
+    };
+    ReefError::reef_native_error(&format!("{}() can't be used on {}", function, kind))
+}
+
+pub fn port_arg(args: &[Value], index: usize, function: &str) -> Result<u16, ReefError> {
+    let port = int_arg(args, index, function)?;
+    u16::try_from(port).map_err(|_| {
+        ReefError::reef_native_error(&format!("{}() port {} is out of range", function, port))
+    })
+}
+
+fn net_error(function: &str, target: &str, error: io::Error) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() failed for {}: {}", function, target, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use crate::testing::{self, connect, free_port};
+
+    #[test]
+    fn serves_and_connects_over_loopback() {
+        let port = free_port();
+        let client = thread::spawn(move || {
+            let mut stream = connect(port);
+            stream.write_all(b"ping\n").expect("write");
+            let mut reply = String::new();
+            BufReader::new(stream).read_line(&mut reply).expect("read");
+            reply
+        });
+        let echo = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let echo_port = echo.local_addr().expect("addr").port();
+        let echo_server = thread::spawn(move || {
+            let (mut stream, _) = echo.accept().expect("accept");
+            stream.write_all(b"\x00hello").expect("write");
+        });
+        let interpreter = testing::run(&format!(
+            "
+            var server = net.listen({port});
+            var bound = server.port();
+            var conn = server.accept();
+            var line = conn.readLine();
+            conn.write(\"pong \" + line + \"
+\");
+            conn.close();
+            server.close();
+            var remote = net.connect(\"127.0.0.1\", {echo_port});
+            var first = remote.read(1);
+            var rest = remote.read(9223372036854775807);
+            var done = remote.read();
+            remote.close();
+        "
+        ))
+        .expect("source should run");
+        assert_eq!(client.join().expect("client thread"), "pong ping\n");
+        echo_server.join().expect("echo thread");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("bound"), port.to_string());
+        assert_eq!(render("line"), "ping");
+        assert_eq!(render("first"), r#"b"\x00""#);
+        assert_eq!(render("rest"), r#"b"hello""#);
+        assert_eq!(render("done"), "nil");
+        assert_eq!(render("conn"), "<closed socket>");
+        assert!(testing::run("net.listen(70000);").is_err());
+        assert!(testing::run("var s = net.listen(0); s.readLine();").is_err());
+    }
+}
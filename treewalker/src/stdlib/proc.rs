@@ -0,0 +1,333 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    io::{self, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio},
+    rc::Rc,
+    thread,
+};
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    map::ReefMap,
+    module::ReefModule,
+    stdlib::{io::read_line, list_arg, string_arg, sys::env_read_lock, type_error},
+};
+
+pub type ProcessRef = Rc<RefCell<ReefProcess>>;
+
+// a spawned child with piped stdin/stdout; stderr goes to the interpreter's own stderr
+#[derive(Debug)]
+pub struct ReefProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    status: Option<ExitStatus>,
+}
+
+impl fmt::Display for ReefProcess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<process {}>", self.child.id())
+    }
+}
+
+pub fn module() -> ReefModule {
+    let proc = ReefModule::new("proc");
+    // proc.run(cmd, args, [{cwd, env, stdin}]) waits for the command and returns {code, stdout, stderr}
+    proc.define_native(NativeFunction::variadic("run", 2, |_interpreter, args| {
+        let mut command = command(&args, "proc.run", &["cwd", "env", "stdin"])?;
+        let input = match option(&args, "stdin") {
+            Some(Value::String(input)) => Some(input.clone()),
+            Some(other) => return Err(type_error("proc.run", 2, "a string stdin", &other)),
+            None => None,
+        };
+        command
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = spawn(&mut command).map_err(|e| spawn_error("proc.run", &args, e))?;
+        // fed from a thread so a child filling its output pipes can't deadlock against us
+        let writer = child.stdin.take().zip(input).map(|(mut stdin, input)| {
+            thread::spawn(move || {
+                // a child that exits without reading its input closes the pipe; that's not our error
+                let _ = stdin.write_all(input.as_bytes());
+            })
+        });
+        let output = child
+            .wait_with_output()
+            .map_err(|e| spawn_error("proc.run", &args, e))?;
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+        let result: ReefMap = [
+            ("code".to_string(), exit_code(output.status)),
+            (
+                "stdout".to_string(),
+                Value::String(String::from_utf8_lossy(&output.stdout).into_owned()),
+            ),
+            (
+                "stderr".to_string(),
+                Value::String(String::from_utf8_lossy(&output.stderr).into_owned()),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        Ok(Value::new_map(result))
+    }));
+    // proc.spawn(cmd, args, [{cwd, env}]) starts the command and returns a handle to talk to it
+    proc.define_native(NativeFunction::variadic(
+        "spawn",
+        2,
+        |_interpreter, args| {
+            let mut command = command(&args, "proc.spawn", &["cwd", "env"])?;
+            command.stdin(Stdio::piped()).stdout(Stdio::piped());
+            let mut child = spawn(&mut command).map_err(|e| spawn_error("proc.spawn", &args, e))?;
+            let process = ReefProcess {
+                stdin: child.stdin.take(),
+                stdout: BufReader::new(child.stdout.take().expect("stdout is piped")),
+                child,
+                status: None,
+            };
+            Ok(Value::Process(Rc::new(RefCell::new(process))))
+        },
+    ));
+    proc
+}
+
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "pid" => NativeFunction::new("pid", 0, |_interpreter, args| {
+            Ok(Value::Int(process_arg(&args)?.borrow().child.id() as i64))
+        }),
+        "write" => NativeFunction::new("write", 1, |interpreter, args| {
+            let process = process_arg(&args)?;
+            let text = interpreter.stringify(&args[1]);
+            let mut process = process.borrow_mut();
+            let stdin = process.stdin.as_mut().ok_or_else(|| {
+                ReefError::reef_native_error("write() on a process whose stdin is closed")
+            })?;
+            stdin
+                .write_all(text.as_bytes())
+                .and_then(|_| stdin.flush())
+                .map_err(|e| process_error("write", e))?;
+            Ok(Value::Nil)
+        }),
+        // signals end of input to children that read until EOF
+        "closeStdin" => NativeFunction::new("closeStdin", 0, |_interpreter, args| {
+            process_arg(&args)?.borrow_mut().stdin = None;
+            Ok(Value::Nil)
+        }),
+        // the next line of stdout without its terminator, or nil once the child closes it
+        "readLine" => NativeFunction::new("readLine", 0, |_interpreter, args| {
+            let process = process_arg(&args)?;
+            read_line(&mut process.borrow_mut().stdout).map_err(|e| process_error("readLine", e))
+        }),
+        // closes stdin and waits for exit; the code is nil when a signal ended the child
+        "wait" => NativeFunction::new("wait", 0, |_interpreter, args| {
+            let process = process_arg(&args)?;
+            let mut process = process.borrow_mut();
+            wait(&mut process).map(exit_code)
+        }),
+        "kill" => NativeFunction::new("kill", 0, |_interpreter, args| {
+            let process = process_arg(&args)?;
+            let mut process = process.borrow_mut();
+            if process.status.is_none() {
+                process.child.kill().map_err(|e| process_error("kill", e))?;
+                wait(&mut process)?;
+            }
+            Ok(Value::Nil)
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn process_arg(args: &[Value]) -> Result<ProcessRef, ReefError> {
+    match &args[0] {
+        Value::Process(process) => Ok(Rc::clone(process)),
+        other => Err(type_error("process method", 0, "a process", other)),
+    }
+}
+
+fn wait(process: &mut ReefProcess) -> Result<ExitStatus, ReefError> {
+    if let Some(status) = process.status {
+        return Ok(status);
+    }
+    process.stdin = None;
+    let status = process.child.wait().map_err(|e| process_error("wait", e))?;
+    process.status = Some(status);
+    Ok(status)
+}
+
+fn exit_code(status: ExitStatus) -> Value {
+    status
+        .code()
+        .map(|code| Value::Int(code as i64))
+        .unwrap_or(Value::Nil)
+}
+
+// builds the command from `cmd`, the argument list and whichever of `allowed` options are given
+fn command(args: &[Value], function: &str, allowed: &[&str]) -> Result<Command, ReefError> {
+    if args.len() > 3 {
+        return Err(ReefError::reef_native_error(&format!(
+            "{}() expects at most 3 args, got {}",
+            function,
+            args.len()
+        )));
+    }
+    let mut command = Command::new(string_arg(args, 0, function)?);
+    for arg in list_arg(args, 1, function)?.borrow().iter() {
+        match arg {
+            Value::String(arg) => command.arg(arg),
+            other => return Err(type_error(function, 1, "a list of strings", other)),
+        };
+    }
+    let Some(options) = args.get(2) else {
+        return Ok(command);
+    };
+    let Value::Map(options) = options else {
+        return Err(type_error(function, 2, "a map of options", options));
+    };
+    for (key, value) in options.borrow().iter() {
+        let Value::String(key) = key else {
+            return Err(type_error(function, 2, "string option names", key));
+        };
+        match (key.as_str(), value) {
+            (key, _) if !allowed.contains(&key) => {
+                return Err(ReefError::reef_native_error(&format!(
+                    "{}() has no option '{}'",
+                    function, key
+                )));
+            }
+            ("cwd", Value::String(dir)) => {
+                command.current_dir(dir);
+            }
+            // added to the inherited environment
+            ("env", Value::Map(vars)) => {
+                for (name, value) in vars.borrow().iter() {
+                    match (name, value) {
+                        (Value::String(name), Value::String(value)) => command.env(name, value),
+                        (Value::String(_), other) | (other, _) => {
+                            return Err(type_error(
+                                function,
+                                2,
+                                "string env names and values",
+                                other,
+                            ));
+                        }
+                    };
+                }
+            }
+            ("stdin", _) => {}
+            (key, other) => {
+                return Err(ReefError::reef_native_error(&format!(
+                    "{}() option '{}' has the wrong type {}",
+                    function,
+                    key,
+                    other.type_name()
+                )));
+            }
+        }
+    }
+    Ok(command)
+}
+
+// the child's environment is read while spawning, which mustn't race sys.setEnv
+fn spawn(command: &mut Command) -> io::Result<Child> {
+    let _env = env_read_lock();
+    command.spawn()
+}
+
+fn option(args: &[Value], name: &str) -> Option<Value> {
+    match args.get(2) {
+        Some(Value::Map(options)) => options.borrow().get(name).cloned(),
+        _ => None,
+    }
+}
+
+fn spawn_error(function: &str, args: &[Value], error: io::Error) -> ReefError {
+    let cmd = match &args[0] {
+        Value::String(cmd) => cmd.as_str(),
+        _ => "",
+    };
+    ReefError::reef_native_error(&format!("{}() failed for '{}': {}", function, cmd, error))
+}
+
+fn process_error(function: &str, error: io::Error) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() failed: {}", function, error))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::{error::ReefError, testing};
+
+    #[test]
+    fn runs_commands_to_completion() {
+        let interpreter = testing::run(
+            "
+            var echoed = proc.run(\"echo\", [\"hello\", \"reef\"]);
+            var piped = proc.run(\"cat\", [], {\"stdin\": \"fed in\"});
+            var failed = proc.run(\"sh\", [\"-c\", \"echo oops >&2; exit 3\"]);
+            var scoped = proc.run(\"sh\", [\"-c\", \"echo $REEF_PROC_TEST; pwd\"], {\"cwd\": \"/\", \"env\": {\"REEF_PROC_TEST\": \"set\"}});
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(
+            render("echoed"),
+            r#"{"code": 0, "stdout": "hello reef\n", "stderr": ""}"#
+        );
+        assert_eq!(
+            render("piped"),
+            r#"{"code": 0, "stdout": "fed in", "stderr": ""}"#
+        );
+        assert_eq!(
+            render("failed"),
+            r#"{"code": 3, "stdout": "", "stderr": "oops\n"}"#
+        );
+        assert_eq!(
+            render("scoped"),
+            r#"{"code": 0, "stdout": "set\n/\n", "stderr": ""}"#
+        );
+    }
+
+    #[test]
+    fn talks_to_spawned_processes() {
+        let interpreter = testing::run(
+            "
+            var child = proc.spawn(\"cat\", []);
+            child.write(\"first
+\");
+            var first = child.readLine();
+            child.write(\"second\");
+            child.closeStdin();
+            var second = child.readLine();
+            var done = child.readLine();
+            var code = child.wait();
+            var sleeper = proc.spawn(\"sleep\", [\"30\"]);
+            sleeper.kill();
+            var killed = sleeper.wait();
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("first"), "first");
+        assert_eq!(render("second"), "second");
+        assert_eq!(render("done"), "nil");
+        assert_eq!(render("code"), "0");
+        assert_eq!(render("killed"), "nil");
+        assert!(matches!(
+            testing::run("proc.run(\"reef-no-such-command\", []);"),
+            Err(ReefError::RuntimeError(..))
+        ));
+        assert!(testing::run("proc.spawn(\"cat\", [], {\"stdin\": \"x\"});").is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    module::ReefModule,
+    stdlib::{int_arg, list_arg},
+};
+
+// xoshiro256** seeded through splitmix64; deterministic for a given seed on every platform
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        let mut mix = seed;
+        let mut next = || {
+            mix = mix.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = mix;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        };
+        Rng {
+            state: [next(), next(), next(), next()],
+        }
+    }
+
+    // seeded from the clock, for scripts that never call `random.seed`
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_nanos() as u64)
+            .unwrap_or(0);
+        Rng::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s1.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = *s1 << 17;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(45);
+        result
+    }
+
+    // uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // uniform in [0, bound) without modulo bias
+    pub fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let n = self.next_u64();
+            if n < zone {
+                return n % bound;
+            }
+        }
+    }
+}
+
+pub fn module() -> ReefModule {
+    let random = ReefModule::new("random");
+    random.define_native(NativeFunction::new("seed", 1, |interpreter, args| {
+        let seed = int_arg(&args, 0, "random.seed")?;
+        interpreter.rng = Rng::new(seed as u64);
+        Ok(Value::Nil)
+    }));
+    random.define_native(NativeFunction::new("float", 0, |interpreter, _args| {
+        Ok(Value::Number(interpreter.rng.next_f64()))
+    }));
+    // both bounds are inclusive
+    random.define_native(NativeFunction::new("int", 2, |interpreter, args| {
+        let low = int_arg(&args, 0, "random.int")?;
+        let high = int_arg(&args, 1, "random.int")?;
+        if low > high {
+            return Err(ReefError::reef_native_error(
+                "random.int() lower bound is greater than upper bound",
+            ));
+        }
+        let span = high.abs_diff(low);
+        let offset = match span.checked_add(1) {
+            Some(bound) => interpreter.rng.below(bound),
+            None => interpreter.rng.next_u64(),
+        };
+        Ok(Value::Int(low.wrapping_add_unsigned(offset)))
+    }));
+    random.define_native(NativeFunction::new("choice", 1, |interpreter, args| {
+        let items = list_arg(&args, 0, "random.choice")?;
+        let items = items.borrow();
+        if items.is_empty() {
+            return Err(ReefError::reef_native_error(
+                "random.choice() from an empty list",
+            ));
+        }
+        let index = interpreter.rng.below(items.len() as u64) as usize;
+        Ok(items[index].clone())
+    }));
+    // shuffles in place
+    random.define_native(NativeFunction::new("shuffle", 1, |interpreter, args| {
+        let items = list_arg(&args, 0, "random.shuffle")?;
+        shuffle_prefix(&mut interpreter.rng, &mut items.borrow_mut(), usize::MAX);
+        Ok(Value::Nil)
+    }));
+    // `k` distinct elements in random order; the list itself is left alone
+    random.define_native(NativeFunction::new("sample", 2, |interpreter, args| {
+        let mut items = list_arg(&args, 0, "random.sample")?.borrow().clone();
+        let k = int_arg(&args, 1, "random.sample")?;
+        let k = usize::try_from(k)
+            .ok()
+            .filter(|k| *k <= items.len())
+            .ok_or_else(|| {
+                ReefError::reef_native_error(&format!(
+                    "random.sample() size {} is out of range for a list of {}",
+                    k,
+                    items.len()
+                ))
+            })?;
+        shuffle_prefix(&mut interpreter.rng, &mut items, k);
+        items.truncate(k);
+        Ok(Value::new_list(items))
+    }));
+    random
+}
+
+// fisher-yates, stopping once the first `count` slots are settled
+fn shuffle_prefix(rng: &mut Rng, items: &mut [Value], count: usize) {
+    let len = items.len();
+    for i in 0..len.saturating_sub(1).min(count) {
+        let j = i + rng.below((len - i) as u64) as usize;
+        items.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+    use crate::testing;
+
+    #[test]
+    fn seeded_sequences_repeat() {
+        let source = "
+            random.seed(42);
+            var rolls = [random.int(1, 6), random.int(1, 6), random.int(1, 6), random.int(-3, -3)];
+            var deck = [1, 2, 3, 4, 5];
+            random.shuffle(deck);
+            var picked = random.sample(deck, 2);
+            var chosen = random.choice(deck);
+            var unit = random.float();
+            var inRange = unit >= 0 and unit < 1;
+        ";
+        let first = testing::run(source).expect("source should run");
+        let second = testing::run(source).expect("source should run");
+        for name in ["rolls", "deck", "picked", "chosen", "unit"] {
+            let a = first.globals.borrow().get(name).expect("defined");
+            let b = second.globals.borrow().get(name).expect("defined");
+            assert_eq!(
+                first.stringify(&a),
+                second.stringify(&b),
+                "{} differs",
+                name
+            );
+        }
+        let globals = first.globals.borrow();
+        let render = |name: &str| first.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("inRange"), "true");
+        assert!(render("rolls").ends_with(", -3]"));
+        let mut deck: Vec<String> = render("deck")
+            .trim_matches(['[', ']'])
+            .split(", ")
+            .map(str::to_string)
+            .collect();
+        deck.sort();
+        assert_eq!(deck, ["1", "2", "3", "4", "5"]);
+    }
+
+    #[test]
+    fn bounded_draws_cover_the_range() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..600 {
+            seen[rng.below(6) as usize] = true;
+        }
+        assert!(seen.iter().all(|hit| *hit));
+        assert!(testing::run("random.int(2, 1);").is_err());
+        assert!(testing::run("random.sample([1], 2);").is_err());
+        assert!(testing::run("random.choice([]);").is_err());
+    }
+}
@@ -0,0 +1,294 @@
+use std::rc::Rc;
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    map::ReefMap,
+    module::ReefModule,
+    regex::Regex,
+    stdlib::{call, string_arg, type_error},
+};
+
+type Captures = Vec<Option<(usize, usize)>>;
+
+const FUNCTIONS: [&str; 5] = ["match", "search", "findAll", "replace", "split"];
+
+pub fn module() -> ReefModule {
+    let re = ReefModule::new("re");
+    re.define_native(NativeFunction::new("compile", 1, |_interpreter, args| {
+        Ok(Value::Regex(regex_arg(&args, 0, "re.compile")?))
+    }));
+    for name in FUNCTIONS {
+        re.define_native(function(name).expect("listed functions exist"));
+    }
+    re
+}
+
+// compiled patterns have the module functions as methods, with the pattern argument bound
+pub fn method(name: &str) -> Option<NativeFunction> {
+    match name {
+        "source" => Some(NativeFunction::new("source", 0, |_interpreter, args| {
+            Ok(Value::String(
+                regex_arg(&args, 0, "source")?.source().to_string(),
+            ))
+        })),
+        // method arity doesn't count the receiver
+        _ => function(name).map(|mut method| {
+            method.arity -= 1;
+            method
+        }),
+    }
+}
+
+// the functions shared by the module and compiled patterns; the pattern is always `args[0]`
+fn function(name: &str) -> Option<NativeFunction> {
+    let function = match name {
+        // anchored at the start of the text
+        "match" => NativeFunction::new("match", 2, |_interpreter, args| {
+            let regex = regex_arg(&args, 0, "re.match")?;
+            let text: Vec<char> = string_arg(&args, 1, "re.match")?.chars().collect();
+            Ok(regex
+                .captures_at(&text, 0, true)
+                .map(|captures| match_value(&regex, &text, &captures))
+                .unwrap_or(Value::Nil))
+        }),
+        "search" => NativeFunction::new("search", 2, |_interpreter, args| {
+            let regex = regex_arg(&args, 0, "re.search")?;
+            let text: Vec<char> = string_arg(&args, 1, "re.search")?.chars().collect();
+            Ok(regex
+                .captures_at(&text, 0, false)
+                .map(|captures| match_value(&regex, &text, &captures))
+                .unwrap_or(Value::Nil))
+        }),
+        // whole matches, or the groups of each match when the pattern has any
+        "findAll" => NativeFunction::new("findAll", 2, |_interpreter, args| {
+            let regex = regex_arg(&args, 0, "re.findAll")?;
+            let text: Vec<char> = string_arg(&args, 1, "re.findAll")?.chars().collect();
+            let found = all_captures(&regex, &text)
+                .iter()
+                .map(|captures| match regex.group_count() {
+                    0 => group_value(&text, captures[0]),
+                    _ => Value::new_list(
+                        captures[1..]
+                            .iter()
+                            .map(|group| group_value(&text, *group))
+                            .collect(),
+                    ),
+                })
+                .collect();
+            Ok(Value::new_list(found))
+        }),
+        // the replacement is a template using `$1`, `${name}` and `$$`, or a function of the match
+        "replace" => NativeFunction::new("replace", 3, |interpreter, args| {
+            let regex = regex_arg(&args, 0, "re.replace")?;
+            let text: Vec<char> = string_arg(&args, 1, "re.replace")?.chars().collect();
+            let mut out = String::new();
+            let mut last = 0;
+            for captures in all_captures(&regex, &text) {
+                let (start, end) = captures[0].expect("a match has a whole group");
+                out.extend(&text[last..start]);
+                match &args[2] {
+                    Value::String(template) => {
+                        out.push_str(&expand(&regex, &text, &captures, template)?)
+                    }
+                    Value::Callable(callable) => {
+                        let matched = match_value(&regex, &text, &captures);
+                        let replacement = call(interpreter, callable, vec![matched], "re.replace")?;
+                        out.push_str(&interpreter.stringify(&replacement));
+                    }
+                    other => {
+                        return Err(type_error("re.replace", 2, "a string or function", other));
+                    }
+                }
+                last = end;
+            }
+            out.extend(&text[last..]);
+            Ok(Value::String(out))
+        }),
+        "split" => NativeFunction::new("split", 2, |_interpreter, args| {
+            let regex = regex_arg(&args, 0, "re.split")?;
+            let text: Vec<char> = string_arg(&args, 1, "re.split")?.chars().collect();
+            let mut parts = Vec::new();
+            let mut last = 0;
+            for captures in all_captures(&regex, &text) {
+                let (start, end) = captures[0].expect("a match has a whole group");
+                // an empty match at either end would only add empty pieces
+                if start == end && (start == 0 || start == text.len()) {
+                    continue;
+                }
+                parts.push(Value::String(text[last..start].iter().collect()));
+                last = end;
+            }
+            parts.push(Value::String(text[last..].iter().collect()));
+            Ok(Value::new_list(parts))
+        }),
+        _ => return None,
+    };
+    Some(function)
+}
+
+// accepts either a compiled pattern or a pattern string
+fn regex_arg(args: &[Value], index: usize, function: &str) -> Result<Rc<Regex>, ReefError> {
+    match &args[index] {
+        Value::Regex(regex) => Ok(Rc::clone(regex)),
+        Value::String(pattern) => Regex::new(pattern).map(Rc::new).map_err(|message| {
+            ReefError::reef_native_error(&format!(
+                "{}() invalid pattern '{}': {}",
+                function, pattern, message
+            ))
+        }),
+        other => Err(type_error(function, index, "a pattern", other)),
+    }
+}
+
+// non-overlapping matches from left to right; an empty match steps over the next char
+fn all_captures(regex: &Regex, text: &[char]) -> Vec<Captures> {
+    let mut found = Vec::new();
+    let mut pos = 0;
+    while pos <= text.len() {
+        let Some(captures) = regex.captures_at(text, pos, false) else {
+            break;
+        };
+        let (start, end) = captures[0].expect("a match has a whole group");
+        pos = if end == start { end + 1 } else { end };
+        found.push(captures);
+    }
+    found
+}
+
+fn group_value(text: &[char], group: Option<(usize, usize)>) -> Value {
+    match group {
+        Some((start, end)) => Value::String(text[start..end].iter().collect()),
+        None => Value::Nil,
+    }
+}
+
+// {"text", "start", "end", "groups", "named"}; unmatched groups are nil
+fn match_value(regex: &Regex, text: &[char], captures: &Captures) -> Value {
+    let (start, end) = captures[0].expect("a match has a whole group");
+    let groups = captures[1..]
+        .iter()
+        .map(|group| group_value(text, *group))
+        .collect();
+    let named: ReefMap = regex
+        .names()
+        .iter()
+        .map(|(name, index)| (name.clone(), group_value(text, captures[*index])))
+        .collect();
+    let result: ReefMap = [
+        ("text".to_string(), group_value(text, captures[0])),
+        ("start".to_string(), Value::Int(start as i64)),
+        ("end".to_string(), Value::Int(end as i64)),
+        ("groups".to_string(), Value::new_list(groups)),
+        ("named".to_string(), Value::new_map(named)),
+    ]
+    .into_iter()
+    .collect();
+    Value::new_map(result)
+}
+
+fn expand(
+    regex: &Regex,
+    text: &[char],
+    captures: &Captures,
+    template: &str,
+) -> Result<String, ReefError> {
+    let mut out = String::new();
+    let mut chars = template.chars().peekable();
+    let group_text = |index: usize| -> Result<String, ReefError> {
+        let group = captures.get(index).ok_or_else(|| {
+            ReefError::reef_native_error(&format!("re.replace() has no group {}", index))
+        })?;
+        Ok(group
+            .map(|(start, end)| text[start..end].iter().collect())
+            .unwrap_or_default())
+    };
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('$') => {
+                chars.next();
+                out.push('$');
+            }
+            Some('{') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let index = match name.parse::<usize>() {
+                    Ok(index) => index,
+                    Err(_) => regex
+                        .names()
+                        .iter()
+                        .find(|(group, _)| *group == name)
+                        .map(|(_, index)| *index)
+                        .ok_or_else(|| {
+                            ReefError::reef_native_error(&format!(
+                                "re.replace() has no group named '{}'",
+                                name
+                            ))
+                        })?,
+                };
+                out.push_str(&group_text(index)?);
+            }
+            Some(d) if d.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    digits.push(*d);
+                    chars.next();
+                }
+                out.push_str(&group_text(digits.parse().expect("ascii digits"))?);
+            }
+            _ => out.push('$'),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::ReefError, testing};
+
+    #[test]
+    fn module_functions_and_compiled_patterns() {
+        let interpreter = testing::run(
+            "
+            var date = re.compile(\"(?P<year>\\d{4})-(\\d\\d)\");
+            var found = date.search(\"due 2024-06, paid 2025-01\");
+            var year = found[\"named\"][\"year\"];
+            var span = [found[\"start\"], found[\"end\"]];
+            var all = date.findAll(\"2024-06 2025-01\");
+            var words = re.findAll(\"\\w+\", \"héllo, wörld\");
+            var swapped = re.replace(\"(\\w+)@(\\w+)\", \"a@b c@d\", \"$2@$1 ${1}$$\");
+            fun shout(m) { return m[\"text\"].upper(); }
+            var loud = re.replace(\"o+\", \"foo boo\", shout);
+            var parts = re.split(\"\\s*,\\s*\", \"a , b,c\");
+            var anchored = re.match(\"b\", \"ab\");
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("year"), "2024");
+        assert_eq!(render("span"), "[4, 11]");
+        assert_eq!(render("all"), r#"[["2024", "06"], ["2025", "01"]]"#);
+        assert_eq!(render("words"), r#"["héllo", "wörld"]"#);
+        assert_eq!(render("swapped"), "b@a a$ d@c c$");
+        assert_eq!(render("loud"), "fOO bOO");
+        assert_eq!(render("parts"), r#"["a", "b", "c"]"#);
+        assert_eq!(render("anchored"), "nil");
+        assert_eq!(render("date"), r"<regex /(?P<year>\d{4})-(\d\d)/>");
+    }
+
+    #[test]
+    fn invalid_patterns_are_runtime_errors() {
+        assert!(testing::run("re.compile(\"(a\");").is_err());
+        assert!(testing::run("re.replace(\"a\", \"a\", \"$2\");").is_err());
+        assert!(matches!(
+            testing::run("re.replace(\"a\", \"abc\", math.pow);"),
+            Err(ReefError::RuntimeError(_))
+        ));
+    }
+}
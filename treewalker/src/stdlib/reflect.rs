@@ -0,0 +1,200 @@
+use std::rc::Rc;
+
+use crate::{
+    Value,
+    class::{ReefClass, ReefClassAttrs, ReefInstanceRef},
+    environment::EnvRef,
+    error::ReefError,
+    func::{NativeFunction, ReefCallable},
+    stdlib::{string_arg, type_error},
+};
+
+pub fn define_natives(globals: &EnvRef) {
+    let natives = [
+        // classes report "class" rather than the generic "function"
+        NativeFunction::new("type", 1, |_interpreter, args| {
+            let name = match &args[0] {
+                Value::Callable(callable) if callable.as_class().is_some() => "class",
+                other => other.type_name(),
+            };
+            Ok(Value::String(name.to_string()))
+        }),
+        NativeFunction::new("fields", 1, |_interpreter, args| {
+            let instance = instance_arg(&args, 0, "fields")?;
+            let names = instance
+                .fields()
+                .into_iter()
+                .map(|(name, _)| Value::String(name))
+                .collect();
+            Ok(Value::new_list(names))
+        }),
+        // methods(classOrInstance), sorted by name like fields
+        NativeFunction::new("methods", 1, |_interpreter, args| {
+            let names = match &args[0] {
+                Value::Instance(instance) => method_names(instance.class()),
+                _ => method_names(class_arg(&args, 0, "methods")?.as_class().expect("a class")),
+            };
+            Ok(Value::new_list(names))
+        }),
+        NativeFunction::new("hasField", 2, |_interpreter, args| {
+            let instance = instance_arg(&args, 0, "hasField")?;
+            let name = string_arg(&args, 1, "hasField")?;
+            Ok(Value::Boolean(
+                !is_private(name) && instance.field(name).is_some(),
+            ))
+        }),
+        // like `obj.name`, so bound methods are found too
+        NativeFunction::new("getField", 2, |interpreter, args| {
+            let instance = instance_arg(&args, 0, "getField")?;
+            let name = public_name(&args, "getField")?;
+            if let Some(value) = instance.field(name) {
+                return Ok(value);
+            }
+            instance
+                .bound_method(interpreter, name)?
+                .map(Value::Callable)
+                .ok_or_else(|| {
+                    ReefError::reef_native_error(&format!(
+                        "getField() {} has no field '{}'",
+                        instance.to_class_string(),
+                        name
+                    ))
+                })
+        }),
+        NativeFunction::new("setField", 3, |_interpreter, args| {
+            let instance = instance_arg(&args, 0, "setField")?;
+            let name = public_name(&args, "setField")?;
+            instance
+                .set_field(name.to_string(), args[2].clone())
+                .map_err(|message| {
+                    ReefError::reef_native_error(&format!("setField() {}", message))
+                })?;
+            Ok(args[2].clone())
+        }),
+        NativeFunction::new("classOf", 1, |_interpreter, args| {
+            let instance = instance_arg(&args, 0, "classOf")?;
+            Ok(Value::Callable(
+                Rc::clone(instance.class()) as Rc<dyn ReefCallable>
+            ))
+        }),
+        // defers to ReefInstance::is_instance_of, which is where inheritance belongs
+        NativeFunction::new("isInstance", 2, |_interpreter, args| {
+            let class = class_arg(&args, 1, "isInstance")?;
+            let Value::Instance(instance) = &args[0] else {
+                return Ok(Value::Boolean(false));
+            };
+            let class = class.as_class().expect("a class");
+            Ok(Value::Boolean(instance.is_instance_of(class)))
+        }),
+        // variadic natives report their minimum
+        NativeFunction::new("arity", 1, |_interpreter, args| match &args[0] {
+            Value::Callable(callable) => Ok(Value::Int(callable.arity() as i64)),
+            other => Err(type_error("arity", 0, "a function", other)),
+        }),
+    ];
+    for native in natives {
+        globals
+            .borrow_mut()
+            .define(native.name.clone(), Value::Callable(Rc::new(native)))
+            .expect("expect reflection function to be definable");
+    }
+}
+
+fn method_names(class: &ReefClass) -> Vec<Value> {
+    let mut names: Vec<&String> = class
+        .methods
+        .keys()
+        .filter(|name| !is_private(name))
+        .collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| Value::String(name.clone()))
+        .collect()
+}
+
+// `#private` members stay reachable only through `this`, so reflection refuses them
+fn is_private(name: &str) -> bool {
+    name.starts_with('#')
+}
+
+fn public_name<'a>(args: &'a [Value], function: &str) -> Result<&'a str, ReefError> {
+    let name = string_arg(args, 1, function)?;
+    if is_private(name) {
+        return Err(ReefError::reef_native_error(&format!(
+            "{}() can't access private member '{}'",
+            function, name
+        )));
+    }
+    Ok(name)
+}
+
+fn instance_arg(
+    args: &[Value],
+    index: usize,
+    function: &str,
+) -> Result<ReefInstanceRef, ReefError> {
+    match &args[index] {
+        Value::Instance(instance) => Ok(Rc::clone(instance)),
+        other => Err(type_error(function, index, "an instance", other)),
+    }
+}
+
+fn class_arg(
+    args: &[Value],
+    index: usize,
+    function: &str,
+) -> Result<Rc<dyn ReefCallable>, ReefError> {
+    match &args[index] {
+        Value::Callable(callable) if callable.as_class().is_some() => Ok(Rc::clone(callable)),
+        other => Err(type_error(function, index, "a class", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+
+    #[test]
+    fn inspects_instances_and_classes() {
+        let interpreter = testing::run(
+            "
+            class Point {
+                init(x, y) { this.x = x; this.y = y; }
+                norm() { return this.x + this.y; }
+            }
+            class Other {}
+            fun one(a) {}
+            var p = Point(1, 2);
+            setField(p, \"label\", \"a\");
+            var names = [fields(p), methods(Point), methods(p)];
+            var lookups = [hasField(p, \"x\"), hasField(p, \"norm\"), getField(p, \"label\"), getField(p, \"norm\")()];
+            var classes = [classOf(p) == Point, isInstance(p, Point), isInstance(p, Other), isInstance(1, Point)];
+            var types = [type(p), type(Point), type(1), type(\"s\"), type((1,)), type(clock)];
+            var arities = [arity(Point), arity(p.norm), arity(one), arity(clock)];
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(
+            render("names"),
+            r#"[["label", "x", "y"], ["init", "norm"], ["init", "norm"]]"#
+        );
+        assert_eq!(render("lookups"), r#"[true, false, "a", 3]"#);
+        assert_eq!(render("classes"), "[true, true, false, false]");
+        assert_eq!(
+            render("types"),
+            r#"["instance", "class", "int", "string", "tuple", "function"]"#
+        );
+        assert_eq!(render("arities"), "[2, 0, 1, 0]");
+    }
+
+    #[test]
+    fn rejects_non_instances() {
+        assert!(testing::run("fields(1);").is_err());
+        assert!(testing::run("class A {} getField(A(), \"missing\");").is_err());
+        assert!(testing::run("class A {} isInstance(A(), 1);").is_err());
+        assert!(testing::run("classOf(\"text\");").is_err());
+    }
+}
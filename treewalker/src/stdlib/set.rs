@@ -0,0 +1,168 @@
+use std::rc::Rc;
+
+use crate::{
+    Value, environment::EnvRef, error::ReefError, expr::SetRef, func::NativeFunction, set::ReefSet,
+    stdlib::type_error,
+};
+
+pub fn define_natives(globals: &EnvRef) {
+    let natives = [
+        // set([values]) builds a set from a list or copies another set
+        NativeFunction::variadic("set", 0, |_interpreter, args| {
+            let values = match args.as_slice() {
+                [] => Vec::new(),
+                [Value::List(items)] => items.borrow().clone(),
+                [Value::Set(members)] => members.borrow().iter().cloned().collect(),
+                [other] => return Err(type_error("set", 0, "a list or set", other)),
+                _ => {
+                    return Err(ReefError::reef_native_error(&format!(
+                        "set() expects at most 1 arg, got {}",
+                        args.len()
+                    )));
+                }
+            };
+            let set = ReefSet::from_values(values).map_err(|message| set_error("set", &message))?;
+            Ok(Value::new_set(set))
+        }),
+    ];
+    for native in natives {
+        globals
+            .borrow_mut()
+            .define(native.name.clone(), Value::Callable(Rc::new(native)))
+            .expect("expect set function to be definable");
+    }
+}
+
+// methods are bound to the receiving set, which arrives as `args[0]`
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "len" => NativeFunction::new("len", 0, |_interpreter, args| {
+            Ok(Value::Int(receiver(&args).borrow().len() as i64))
+        }),
+        // add and remove report whether the set changed
+        "add" => NativeFunction::new("add", 1, |_interpreter, args| {
+            let added = receiver(&args)
+                .borrow_mut()
+                .insert(args[1].clone())
+                .map_err(|message| set_error("add", &message))?;
+            Ok(Value::Boolean(added))
+        }),
+        "remove" => NativeFunction::new("remove", 1, |_interpreter, args| {
+            let removed = receiver(&args)
+                .borrow_mut()
+                .remove(&args[1])
+                .map_err(|message| set_error("remove", &message))?;
+            Ok(Value::Boolean(removed))
+        }),
+        "has" => NativeFunction::new("has", 1, |_interpreter, args| {
+            let found = receiver(&args)
+                .borrow()
+                .contains(&args[1])
+                .map_err(|message| set_error("has", &message))?;
+            Ok(Value::Boolean(found))
+        }),
+        "toList" => NativeFunction::new("toList", 0, |_interpreter, args| {
+            let members = receiver(&args).borrow().iter().cloned().collect();
+            Ok(Value::new_list(members))
+        }),
+        // the algebra returns new sets, keeping the receiver's members first
+        "union" => NativeFunction::new("union", 1, |_interpreter, args| {
+            let other = set_arg(&args, "union")?;
+            let members: Vec<Value> = receiver(&args)
+                .borrow()
+                .iter()
+                .chain(other.borrow().iter())
+                .cloned()
+                .collect();
+            let union = ReefSet::from_values(members).expect("members are already keys");
+            Ok(Value::new_set(union))
+        }),
+        "intersection" => NativeFunction::new("intersection", 1, |_interpreter, args| {
+            let other = set_arg(&args, "intersection")?;
+            Ok(Value::new_set(filter(&args, &other, true)))
+        }),
+        "difference" => NativeFunction::new("difference", 1, |_interpreter, args| {
+            let other = set_arg(&args, "difference")?;
+            Ok(Value::new_set(filter(&args, &other, false)))
+        }),
+        "isSubset" => NativeFunction::new("isSubset", 1, |_interpreter, args| {
+            let other = set_arg(&args, "isSubset")?;
+            Ok(Value::Boolean(is_subset(receiver(&args), &other)))
+        }),
+        "isSuperset" => NativeFunction::new("isSuperset", 1, |_interpreter, args| {
+            let other = set_arg(&args, "isSuperset")?;
+            Ok(Value::Boolean(is_subset(&other, receiver(&args))))
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+// the receiver's members that are (or aren't) in `other`
+fn filter(args: &[Value], other: &SetRef, keep_shared: bool) -> ReefSet {
+    let other = other.borrow();
+    let members: Vec<Value> = receiver(args)
+        .borrow()
+        .iter()
+        .filter(|member| other.contains(member) == Ok(keep_shared))
+        .cloned()
+        .collect();
+    ReefSet::from_values(members).expect("members are already keys")
+}
+
+fn is_subset(set: &SetRef, of: &SetRef) -> bool {
+    let of = of.borrow();
+    set.borrow()
+        .iter()
+        .all(|member| of.contains(member) == Ok(true))
+}
+
+fn set_arg(args: &[Value], function: &str) -> Result<SetRef, ReefError> {
+    match &args[1] {
+        Value::Set(members) => Ok(Rc::clone(members)),
+        other => Err(type_error(function, 0, "a set", other)),
+    }
+}
+
+fn set_error(function: &str, message: &str) -> ReefError {
+    ReefError::reef_native_error(&format!("{}() {}", function, message))
+}
+
+fn receiver(args: &[Value]) -> &SetRef {
+    match &args[0] {
+        Value::Set(members) => members,
+        _ => unreachable!("set methods are only bound to sets"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+
+    #[test]
+    fn combines_sets() {
+        let interpreter = testing::run(
+            "
+            var a = set([1, 2, 3]);
+            var b = #{3, 4};
+            var added = [a.add(4), a.add(4), a.remove(1), a.remove(1)];
+            var union = a.union(b);
+            var both = a.intersection(b);
+            var onlyA = a.difference(b);
+            var subset = [b.isSubset(a), a.isSuperset(b), a.isSubset(b), set().isSubset(b)];
+            var members = [a.len(), a.has(2), a.toList()];
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("added"), "[true, false, true, false]");
+        assert_eq!(render("union"), "#{2, 3, 4}");
+        assert_eq!(render("both"), "#{3, 4}");
+        assert_eq!(render("onlyA"), "#{2}");
+        assert_eq!(render("subset"), "[true, true, false, true]");
+        assert_eq!(render("members"), "[3, true, [2, 3, 4]]");
+        assert!(testing::run("set([[1]]);").is_err());
+        assert!(testing::run("#{1}.union([1]);").is_err());
+    }
+}
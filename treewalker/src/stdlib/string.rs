@@ -0,0 +1,250 @@
+use bytes::Bytes;
+
+use crate::{
+    Value,
+    bigint::BigInt,
+    error::ReefError,
+    func::NativeFunction,
+    number,
+    stdlib::{expect_at_most, int_arg, list_arg, string_arg},
+};
+
+// strings built by repeat and pad are capped so a bad count is an error, not an abort
+const MAX_LEN: usize = 256 * 1024 * 1024;
+
+// methods are bound to the receiving string, which arrives as `args[0]`
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "len" => NativeFunction::new("len", 0, |_interpreter, args| {
+            Ok(Value::Int(receiver(&args).chars().count() as i64))
+        }),
+        "upper" => NativeFunction::new("upper", 0, |_interpreter, args| {
+            Ok(Value::String(receiver(&args).to_uppercase()))
+        }),
+        "lower" => NativeFunction::new("lower", 0, |_interpreter, args| {
+            Ok(Value::String(receiver(&args).to_lowercase()))
+        }),
+        "trim" => NativeFunction::new("trim", 0, |_interpreter, args| {
+            Ok(Value::String(receiver(&args).trim().to_string()))
+        }),
+        "split" => NativeFunction::variadic("split", 0, |_interpreter, args| {
+            expect_at_most(&args[1..], 1, "split")?;
+            let s = receiver(&args);
+            let parts: Vec<Value> = match args.get(1) {
+                None => s
+                    .split_whitespace()
+                    .map(|part| Value::String(part.to_string()))
+                    .collect(),
+                Some(_) => match string_arg(&args[1..], 0, "split")? {
+                    "" => s.chars().map(|c| Value::String(c.to_string())).collect(),
+                    separator => s
+                        .split(separator)
+                        .map(|part| Value::String(part.to_string()))
+                        .collect(),
+                },
+            };
+            Ok(Value::new_list(parts))
+        }),
+        "join" => NativeFunction::new("join", 1, |interpreter, args| {
+            let items = list_arg(&args[1..], 0, "join")?;
+            let parts: Vec<String> = items
+                .borrow()
+                .iter()
+                .map(|item| interpreter.stringify(item))
+                .collect();
+            Ok(Value::String(parts.join(receiver(&args))))
+        }),
+        "replace" => NativeFunction::new("replace", 2, |_interpreter, args| {
+            let from = string_arg(&args[1..], 0, "replace")?;
+            let to = string_arg(&args[1..], 1, "replace")?;
+            Ok(Value::String(receiver(&args).replace(from, to)))
+        }),
+        "startsWith" => NativeFunction::new("startsWith", 1, |_interpreter, args| {
+            let prefix = string_arg(&args[1..], 0, "startsWith")?;
+            Ok(Value::Boolean(receiver(&args).starts_with(prefix)))
+        }),
+        "endsWith" => NativeFunction::new("endsWith", 1, |_interpreter, args| {
+            let suffix = string_arg(&args[1..], 0, "endsWith")?;
+            Ok(Value::Boolean(receiver(&args).ends_with(suffix)))
+        }),
+        "find" => NativeFunction::new("find", 1, |_interpreter, args| {
+            let s = receiver(&args);
+            let needle = string_arg(&args[1..], 0, "find")?;
+            let index = s
+                .find(needle)
+                .map(|byte| s[..byte].chars().count() as i64)
+                .unwrap_or(-1);
+            Ok(Value::Int(index))
+        }),
+        "slice" => NativeFunction::variadic("slice", 1, |_interpreter, args| {
+            expect_at_most(&args[1..], 2, "slice")?;
+            let chars: Vec<char> = receiver(&args).chars().collect();
+            let start = int_arg(&args[1..], 0, "slice")?;
+            let end = match args.get(2) {
+                Some(_) => Some(int_arg(&args[1..], 1, "slice")?),
+                None => None,
+            };
+            let (start, end) = slice_bounds(start, end, chars.len());
+            Ok(Value::String(chars[start..end].iter().collect()))
+        }),
+        "repeat" => NativeFunction::new("repeat", 1, |_interpreter, args| {
+            let count = int_arg(&args[1..], 0, "repeat")?;
+            let count = usize::try_from(count)
+                .map_err(|_| ReefError::reef_native_error("repeat() count must not be negative"))?;
+            let s = receiver(&args);
+            checked_len(s.len().checked_mul(count), "repeat")?;
+            Ok(Value::String(s.repeat(count)))
+        }),
+        // the utf-8 encoding of the string
+        "encode" => NativeFunction::new("encode", 0, |_interpreter, args| {
+            Ok(Value::Bytes(Bytes::copy_from_slice(
+                receiver(&args).as_bytes(),
+            )))
+        }),
+        "chars" => NativeFunction::new("chars", 0, |_interpreter, args| {
+            let chars = receiver(&args)
+                .chars()
+                .map(|c| Value::String(c.to_string()))
+                .collect();
+            Ok(Value::new_list(chars))
+        }),
+        "padLeft" => NativeFunction::variadic("padLeft", 1, |_interpreter, args| {
+            let (s, padding) = pad(&args, "padLeft")?;
+            Ok(Value::String(format!("{}{}", padding, s)))
+        }),
+        "padRight" => NativeFunction::variadic("padRight", 1, |_interpreter, args| {
+            let (s, padding) = pad(&args, "padRight")?;
+            Ok(Value::String(format!("{}{}", s, padding)))
+        }),
+        "toNumber" => NativeFunction::new("toNumber", 0, |_interpreter, args| {
+            Ok(parse_number(receiver(&args)))
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn receiver(args: &[Value]) -> &str {
+    match &args[0] {
+        Value::String(s) => s,
+        _ => unreachable!("string methods are only bound to strings"),
+    }
+}
+
+// python-style slice bounds: negatives count from the end and everything clamps to the string
+pub fn slice_bounds(start: i64, end: Option<i64>, len: usize) -> (usize, usize) {
+    let resolve = |index: i64| {
+        let index = if index < 0 { index + len as i64 } else { index };
+        index.clamp(0, len as i64) as usize
+    };
+    let start = resolve(start);
+    let end = end.map(resolve).unwrap_or(len);
+    (start, end.max(start))
+}
+
+// returns the receiver and the padding needed to reach the requested width
+fn pad<'a>(args: &'a [Value], function: &str) -> Result<(&'a str, String), ReefError> {
+    expect_at_most(&args[1..], 2, function)?;
+    let s = receiver(args);
+    let width = int_arg(&args[1..], 0, function)?;
+    let fill = match args.get(2) {
+        Some(_) => string_arg(&args[1..], 1, function)?,
+        None => " ",
+    };
+    let mut fill_chars = fill.chars();
+    let fill = match (fill_chars.next(), fill_chars.next()) {
+        (Some(c), None) => c,
+        _ => {
+            return Err(ReefError::reef_native_error(&format!(
+                "{}() fill must be a single character",
+                function
+            )));
+        }
+    };
+    let width = usize::try_from(width.max(0)).unwrap_or(usize::MAX);
+    let missing = width.saturating_sub(s.chars().count());
+    let padding = missing.checked_mul(fill.len_utf8());
+    checked_len(
+        padding.and_then(|padding| padding.checked_add(s.len())),
+        function,
+    )?;
+    Ok((s, fill.to_string().repeat(missing)))
+}
+
+// `len` is the byte length of the result, or none if computing it overflowed
+fn checked_len(len: Option<usize>, function: &str) -> Result<(), ReefError> {
+    match len {
+        Some(len) if len <= MAX_LEN => Ok(()),
+        _ => Err(ReefError::reef_native_error(&format!(
+            "{}() result would be longer than {} bytes",
+            function, MAX_LEN
+        ))),
+    }
+}
+
+// nil when the text isn't a number, so scripts can test the result
+fn parse_number(text: &str) -> Value {
+    let text = text.trim();
+    if let Ok(n) = text.parse::<i64>() {
+        return Value::Int(n);
+    }
+    if let Some(n) = BigInt::parse_radix(text, 10) {
+        return number::from_bigint(n);
+    }
+    match text.parse::<f64>() {
+        Ok(n) => Value::Number(n),
+        Err(_) => Value::Nil,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+
+    #[test]
+    fn string_methods() {
+        let interpreter = testing::run(
+            "
+            var words = \"  a,b,,c \".trim().split(\",\");
+            var joined = \"-\".join(words);
+            var sliced = \"héllo wörld\".slice(-5);
+            var found = \"héllo\".find(\"llo\");
+            var padded = \"7\".padLeft(3, \"0\") + \"x\".padRight(3);
+            var shout = \"abc\".upper().repeat(2);
+            var letters = \"añb\".chars();
+            var parsed = \"42\".toNumber() + \" 1.5 \".toNumber();
+            var missing = \"abc\".toNumber();
+            var length = \"héllo\".len();
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("words"), r#"["a", "b", "", "c"]"#);
+        assert_eq!(render("joined"), "a-b--c");
+        assert_eq!(render("sliced"), "wörld");
+        assert_eq!(render("found"), "2");
+        assert_eq!(render("padded"), "007x  ");
+        assert_eq!(render("shout"), "ABCABC");
+        assert_eq!(render("letters"), r#"["a", "ñ", "b"]"#);
+        assert_eq!(render("parsed"), "43.5");
+        assert_eq!(render("missing"), "nil");
+        assert_eq!(render("length"), "5");
+    }
+
+    #[test]
+    fn string_method_errors() {
+        for source in [
+            "\"abc\".nope();",
+            "\"abc\".repeat(-1);",
+            "\"abc\".split(1);",
+            "\"abc\".padLeft(5, \"ab\");",
+            "\"abc\".split(\",\", 1);",
+            "\"abc\".padRight(5, \"-\", 1);",
+            "\"abc\".repeat(6148914691236517206);",
+            "\"abc\".padLeft(9223372036854775807, \"é\");",
+        ] {
+            assert!(testing::run(source).is_err(), "{} should fail", source);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    env,
+    sync::{PoisonError, RwLock, RwLockReadGuard},
+};
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    module::ReefModule,
+    stdlib::{expect_at_most, int_arg, string_arg},
+};
+
+// every read of the environment the stdlib makes, directly or through libc (spawning processes,
+// resolving host names), holds this shared; setEnv holds it exclusively
+static ENV_LOCK: RwLock<()> = RwLock::new(());
+
+pub fn env_read_lock() -> RwLockReadGuard<'static, ()> {
+    ENV_LOCK.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn module(args: Vec<String>) -> ReefModule {
+    let sys = ReefModule::new("sys");
+    sys.define(
+        "args",
+        Value::new_list(args.into_iter().map(Value::String).collect()),
+    );
+    sys.define("platform", Value::String(env::consts::OS.to_string()));
+    sys.define(
+        "version",
+        Value::String(env!("CARGO_PKG_VERSION").to_string()),
+    );
+
+    // nil for unset variables or values that aren't valid unicode
+    sys.define_native(NativeFunction::new("env", 1, |_interpreter, args| {
+        let name = string_arg(&args, 0, "sys.env")?;
+        let _env = env_read_lock();
+        Ok(env::var(name).map(Value::String).unwrap_or(Value::Nil))
+    }));
+    sys.define_native(NativeFunction::new("setEnv", 2, |_interpreter, args| {
+        let name = string_arg(&args, 0, "sys.setEnv")?;
+        let value = string_arg(&args, 1, "sys.setEnv")?;
+        if name.is_empty() || name.contains(['=', '\0']) {
+            return Err(ReefError::reef_native_error(&format!(
+                "sys.setEnv() invalid variable name '{}'",
+                name.escape_debug()
+            )));
+        }
+        if value.contains('\0') {
+            return Err(ReefError::reef_native_error(&format!(
+                "sys.setEnv() value for '{}' contains a NUL byte",
+                name
+            )));
+        }
+        let _env = ENV_LOCK.write().unwrap_or_else(PoisonError::into_inner);
+        // SAFETY: set_var is only unsound alongside a concurrent read that bypasses std's own
+        // environment lock. Within the interpreter those reads are libc's (process spawning and
+        // host name lookups), and they all hold ENV_LOCK, which we hold exclusively here. The
+        // crate links no other foreign code that reads the environment.
+        unsafe { env::set_var(name, value) };
+        Ok(Value::Nil)
+    }));
+    sys.define_native(NativeFunction::variadic("exit", 0, |_interpreter, args| {
+        expect_at_most(&args, 1, "sys.exit")?;
+        let code = match args.first() {
+            Some(_) => int_arg(&args, 0, "sys.exit")?,
+            None => 0,
+        };
+        let code = i32::try_from(code).map_err(|_| {
+            ReefError::reef_native_error(&format!("sys.exit() code {} is out of range", code))
+        })?;
+        Err(ReefError::reef_exit(code))
+    }));
+    sys
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::ReefError, testing};
+
+    #[test]
+    fn exit_unwinds_with_status() {
+        let source = "
+            fun bail() { sys.exit(3); }
+            bail();
+            var unreachable = true;
+        ";
+        assert!(matches!(testing::run(source), Err(ReefError::Exit(3))));
+        assert!(matches!(
+            testing::run("sys.exit();"),
+            Err(ReefError::Exit(0))
+        ));
+        assert!(matches!(
+            testing::run("sys.exit(1, 2);"),
+            Err(ReefError::RuntimeError(_))
+        ));
+    }
+
+    #[test]
+    fn environment_round_trips() {
+        let interpreter = testing::run(
+            "
+            sys.setEnv(\"REEF_SYS_TEST\", \"on\");
+            var value = sys.env(\"REEF_SYS_TEST\");
+            var missing = sys.env(\"REEF_SYS_TEST_UNSET\");
+            var args = sys.args;
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("value"), "on");
+        assert_eq!(render("missing"), "nil");
+        assert_eq!(render("args"), "[]");
+    }
+
+    #[test]
+    fn set_env_errors_name_the_bad_argument() {
+        for (source, expected) in [
+            ("sys.setEnv(\"A=B\", \"on\");", "invalid variable name"),
+            ("sys.setEnv(\"A\0B\", \"on\");", "invalid variable name"),
+            (
+                "sys.setEnv(\"REEF_SYS_NUL\", \"o\0n\");",
+                "value for 'REEF_SYS_NUL'",
+            ),
+        ] {
+            let error = testing::run(source).expect_err("setEnv should refuse");
+            assert!(format!("{:?}", error).contains(expected), "{}", source);
+        }
+    }
+}
@@ -0,0 +1,665 @@
+use std::{
+    sync::OnceLock,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    Value,
+    error::ReefError,
+    func::NativeFunction,
+    map::ReefMap,
+    module::ReefModule,
+    stdlib::{expect_at_most, int_arg, number_arg, string_arg, type_error},
+};
+
+// timestamps are epoch seconds and durations are seconds, so plain arithmetic combines them
+
+const DAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+// `time.instant()` values count from the first call, since `Instant` has no fixed epoch
+static START: OnceLock<Instant> = OnceLock::new();
+
+macro_rules! duration_fn {
+    ($module:expr, $name:literal, $seconds:expr) => {
+        $module.define_native(NativeFunction::new($name, 1, |_interpreter, args| {
+            let n = number_arg(&args, 0, concat!("time.", $name))?;
+            Ok(Value::Number(n * $seconds))
+        }))
+    };
+}
+
+pub fn module() -> ReefModule {
+    let time = ReefModule::new("time");
+    time.define_native(NativeFunction::new("now", 0, |_interpreter, _args| {
+        let since = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| ReefError::reef_native_error("time.now() clock is before 1970"))?;
+        Ok(Value::Number(since.as_secs_f64()))
+    }));
+    time.define_native(NativeFunction::new("instant", 0, |_interpreter, _args| {
+        let start = START.get_or_init(Instant::now);
+        Ok(Value::Number(start.elapsed().as_secs_f64()))
+    }));
+    time.define_native(NativeFunction::new("elapsed", 1, |_interpreter, args| {
+        let since = number_arg(&args, 0, "time.elapsed")?;
+        let start = START.get_or_init(Instant::now);
+        Ok(Value::Number(start.elapsed().as_secs_f64() - since))
+    }));
+    time.define_native(NativeFunction::new("sleep", 1, |_interpreter, args| {
+        let millis = number_arg(&args, 0, "time.sleep")?;
+        if !(millis >= 0.0 && millis.is_finite()) {
+            return Err(ReefError::reef_native_error(
+                "time.sleep() expects a non-negative number of milliseconds",
+            ));
+        }
+        let duration = Duration::try_from_secs_f64(millis / 1000.0).map_err(|_| {
+            ReefError::reef_native_error(&format!(
+                "time.sleep() {} milliseconds is too long",
+                millis
+            ))
+        })?;
+        thread::sleep(duration);
+        Ok(Value::Nil)
+    }));
+    duration_fn!(time, "millis", 0.001);
+    duration_fn!(time, "seconds", 1.0);
+    duration_fn!(time, "minutes", 60.0);
+    duration_fn!(time, "hours", 3600.0);
+    duration_fn!(time, "days", 86400.0);
+
+    // format(timestamp, pattern, [offset]) with strftime-style directives
+    time.define_native(NativeFunction::variadic(
+        "format",
+        2,
+        |_interpreter, args| {
+            expect_at_most(&args, 3, "time.format")?;
+            let timestamp = number_arg(&args, 0, "time.format")?;
+            let pattern = string_arg(&args, 1, "time.format")?;
+            let offset = offset_arg(&args, 2, "time.format")?;
+            let parts = DateTime::from_timestamp(timestamp, offset)?;
+            format(&parts, pattern).map(Value::String)
+        },
+    ));
+    // parse(text, [pattern]); without a pattern the text is read as ISO 8601
+    time.define_native(NativeFunction::variadic(
+        "parse",
+        1,
+        |_interpreter, args| {
+            expect_at_most(&args, 2, "time.parse")?;
+            let text = string_arg(&args, 0, "time.parse")?;
+            let parsed = match args.get(1) {
+                Some(_) => parse(text, string_arg(&args, 1, "time.parse")?),
+                None => parse_iso(text),
+            };
+            let parts = parsed.map_err(|message| {
+                ReefError::reef_native_error(&format!(
+                    "time.parse() cannot parse '{}': {}",
+                    text, message
+                ))
+            })?;
+            Ok(Value::Number(parts.timestamp()?))
+        },
+    ));
+    time.define_native(NativeFunction::variadic(
+        "parts",
+        1,
+        |_interpreter, args| {
+            expect_at_most(&args, 2, "time.parts")?;
+            let timestamp = number_arg(&args, 0, "time.parts")?;
+            let offset = offset_arg(&args, 1, "time.parts")?;
+            let parts = DateTime::from_timestamp(timestamp, offset)?;
+            let map: ReefMap = [
+                ("year", Value::Int(parts.year)),
+                ("month", Value::Int(parts.month)),
+                ("day", Value::Int(parts.day)),
+                ("hour", Value::Int(parts.hour)),
+                ("minute", Value::Int(parts.minute)),
+                ("second", Value::Int(parts.second)),
+                ("nanosecond", Value::Int(parts.nanosecond)),
+                ("weekday", Value::Int(parts.weekday())),
+                ("yearday", Value::Int(parts.yearday())),
+                ("offset", Value::Int(parts.offset / 60)),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect();
+            Ok(Value::new_map(map))
+        },
+    ));
+    // date(year, month, day, [hour, minute, second, offset])
+    time.define_native(NativeFunction::variadic("date", 3, |_interpreter, args| {
+        if args.len() > 7 {
+            return Err(ReefError::reef_native_error(
+                "time.date() takes at most 7 arguments",
+            ));
+        }
+        let mut fields = [0i64; 6];
+        for (index, field) in fields.iter_mut().enumerate().take(args.len().min(6)) {
+            *field = int_arg(&args, index, "time.date")?;
+        }
+        let parts = DateTime {
+            year: fields[0],
+            month: fields[1],
+            day: fields[2],
+            hour: fields[3],
+            minute: fields[4],
+            second: fields[5],
+            nanosecond: 0,
+            offset: offset_arg(&args, 6, "time.date")?,
+        };
+        parts
+            .validate()
+            .map_err(|message| ReefError::reef_native_error(&format!("time.date() {}", message)))?;
+        Ok(Value::Number(parts.timestamp()?))
+    }));
+    time
+}
+
+// offsets are minutes east of UTC or strings like "+05:30" and "Z"; missing means UTC
+fn offset_arg(args: &[Value], index: usize, function: &str) -> Result<i64, ReefError> {
+    let invalid = |text: &str| {
+        ReefError::reef_native_error(&format!("{}() invalid UTC offset '{}'", function, text))
+    };
+    let minutes = match args.get(index) {
+        None | Some(Value::Nil) => 0,
+        Some(Value::Int(minutes)) => *minutes,
+        Some(Value::String(text)) => {
+            let mut chars: Vec<char> = text.chars().collect();
+            chars.push('\0');
+            let mut reader = Reader {
+                chars: &chars,
+                current: 0,
+            };
+            let seconds = reader.offset().map_err(|_| invalid(text))?;
+            if reader.current != chars.len() - 1 {
+                return Err(invalid(text));
+            }
+            seconds / 60
+        }
+        Some(other) => return Err(type_error(function, index, "a UTC offset", other)),
+    };
+    if minutes.unsigned_abs() >= 24 * 60 {
+        return Err(invalid(&minutes.to_string()));
+    }
+    Ok(minutes * 60)
+}
+
+// a civil date and time at a fixed offset (in seconds) from UTC
+#[derive(Debug, Clone, PartialEq)]
+struct DateTime {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    nanosecond: i64,
+    offset: i64,
+}
+
+impl DateTime {
+    fn from_timestamp(timestamp: f64, offset: i64) -> Result<DateTime, ReefError> {
+        // roughly the years -100000..100000, far inside i64 once converted
+        if !timestamp.is_finite() || timestamp.abs() > 3.2e15 {
+            return Err(ReefError::reef_native_error(&format!(
+                "timestamp {} is out of range",
+                timestamp
+            )));
+        }
+        let whole = timestamp.floor();
+        let nanosecond = ((timestamp - whole) * 1e9).round().min(999_999_999.0) as i64;
+        let local = whole as i64 + offset;
+        let days = local.div_euclid(86400);
+        let seconds = local.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+        Ok(DateTime {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds % 3600 / 60,
+            second: seconds % 60,
+            nanosecond,
+            offset,
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=12).contains(&self.month) {
+            return Err(format!("month {} is out of range", self.month));
+        }
+        if !(1..=days_in_month(self.year, self.month)).contains(&self.day) {
+            return Err(format!("day {} is out of range", self.day));
+        }
+        if !(0..24).contains(&self.hour)
+            || !(0..60).contains(&self.minute)
+            || !(0..=60).contains(&self.second)
+        {
+            return Err("time of day is out of range".to_string());
+        }
+        Ok(())
+    }
+
+    fn timestamp(&self) -> Result<f64, ReefError> {
+        if self.year.unsigned_abs() > 100_000 {
+            return Err(ReefError::reef_native_error(&format!(
+                "year {} is out of range",
+                self.year
+            )));
+        }
+        let days = days_from_civil(self.year, self.month, self.day);
+        let seconds = days * 86400 + self.hour * 3600 + self.minute * 60 + self.second;
+        Ok((seconds - self.offset) as f64 + self.nanosecond as f64 / 1e9)
+    }
+
+    // 0 is Sunday
+    fn weekday(&self) -> i64 {
+        (days_from_civil(self.year, self.month, self.day) + 4).rem_euclid(7)
+    }
+
+    // 1 is January 1st
+    fn yearday(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) - days_from_civil(self.year, 1, 1) + 1
+    }
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// days since 1970-01-01 in the proleptic gregorian calendar (Howard Hinnant's algorithm)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn format_offset(offset: i64, colon: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let minutes = offset.abs() / 60;
+    let separator = if colon { ":" } else { "" };
+    format!(
+        "{}{:02}{}{:02}",
+        sign,
+        minutes / 60,
+        separator,
+        minutes % 60
+    )
+}
+
+fn format(parts: &DateTime, pattern: &str) -> Result<String, ReefError> {
+    let mut out = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let directive = chars.next().ok_or_else(|| {
+            ReefError::reef_native_error("time.format() pattern ends with a lone '%'")
+        })?;
+        let hour12 = match parts.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        let text = match directive {
+            'Y' => format!("{:04}", parts.year),
+            'y' => format!("{:02}", parts.year.rem_euclid(100)),
+            'm' => format!("{:02}", parts.month),
+            'd' => format!("{:02}", parts.day),
+            'e' => format!("{:2}", parts.day),
+            'H' => format!("{:02}", parts.hour),
+            'I' => format!("{:02}", hour12),
+            'M' => format!("{:02}", parts.minute),
+            'S' => format!("{:02}", parts.second),
+            'f' => format!("{:06}", parts.nanosecond / 1000),
+            'L' => format!("{:03}", parts.nanosecond / 1_000_000),
+            'p' => (if parts.hour < 12 { "AM" } else { "PM" }).to_string(),
+            'j' => format!("{:03}", parts.yearday()),
+            'a' => DAYS[parts.weekday() as usize][..3].to_string(),
+            'A' => DAYS[parts.weekday() as usize].to_string(),
+            'w' => parts.weekday().to_string(),
+            'b' => MONTHS[parts.month as usize - 1][..3].to_string(),
+            'B' => MONTHS[parts.month as usize - 1].to_string(),
+            'z' => format_offset(parts.offset, false),
+            ':' if chars.as_str().starts_with('z') => {
+                chars.next();
+                format_offset(parts.offset, true)
+            }
+            'Z' if parts.offset == 0 => "UTC".to_string(),
+            'Z' => format_offset(parts.offset, true),
+            's' => (parts.timestamp()? as i64).to_string(),
+            'F' => format!("{:04}-{:02}-{:02}", parts.year, parts.month, parts.day),
+            'T' => format!("{:02}:{:02}:{:02}", parts.hour, parts.minute, parts.second),
+            '%' => "%".to_string(),
+            other => {
+                return Err(ReefError::reef_native_error(&format!(
+                    "time.format() unknown directive '%{}'",
+                    other
+                )));
+            }
+        };
+        out.push_str(&text);
+    }
+    Ok(out)
+}
+
+struct Reader<'a> {
+    // always ends with a '\0' sentinel so lookahead never runs off the end
+    chars: &'a [char],
+    current: usize,
+}
+
+impl Reader<'_> {
+    fn peek(&self) -> char {
+        self.chars[self.current.min(self.chars.len() - 1)]
+    }
+
+    fn literal(&mut self, expected: char) -> Result<(), String> {
+        if self.peek() != expected {
+            return Err(format!(
+                "expected '{}' at offset {}",
+                expected, self.current
+            ));
+        }
+        self.current += 1;
+        Ok(())
+    }
+
+    // between 1 and `max` digits, or exactly `max` when `exact`
+    fn number(&mut self, max: usize, exact: bool) -> Result<i64, String> {
+        let start = self.current;
+        while self.current - start < max && self.peek().is_ascii_digit() {
+            self.current += 1;
+        }
+        let count = self.current - start;
+        if count == 0 || (exact && count != max) {
+            return Err(format!("expected {} digits at offset {}", max, start));
+        }
+        let digits: String = self.chars[start..self.current].iter().collect();
+        Ok(digits.parse().expect("ascii digits"))
+    }
+
+    // fractional seconds as nanoseconds
+    fn fraction(&mut self) -> Result<i64, String> {
+        let start = self.current;
+        let digits = self.number(9, false)?;
+        Ok(digits * 10i64.pow((9 - (self.current - start)) as u32))
+    }
+
+    // "Z", "+hh:mm", "+hhmm" or "+hh", as seconds east of UTC
+    fn offset(&mut self) -> Result<i64, String> {
+        let sign = match self.peek() {
+            'Z' | 'z' => {
+                self.current += 1;
+                return Ok(0);
+            }
+            '+' => 1,
+            '-' => -1,
+            _ => return Err(format!("expected UTC offset at offset {}", self.current)),
+        };
+        self.current += 1;
+        let hours = self.number(2, true)?;
+        if self.peek() == ':' {
+            self.current += 1;
+        }
+        let minutes = if self.peek().is_ascii_digit() {
+            self.number(2, true)?
+        } else {
+            0
+        };
+        if hours >= 24 || minutes >= 60 {
+            return Err("UTC offset out of range".to_string());
+        }
+        Ok(sign * (hours * 3600 + minutes * 60))
+    }
+
+    fn month_name(&mut self) -> Result<i64, String> {
+        let rest: String = self.chars[self.current..]
+            .iter()
+            .take(3)
+            .collect::<String>()
+            .to_lowercase();
+        let index = MONTHS
+            .iter()
+            .position(|month| month[..3].to_lowercase() == rest)
+            .ok_or_else(|| format!("expected month name at offset {}", self.current))?;
+        self.current += 3;
+        // accept the full name too
+        let full = &MONTHS[index][3..];
+        let tail: String = self.chars[self.current..].iter().take(full.len()).collect();
+        if tail.eq_ignore_ascii_case(full) {
+            self.current += full.len();
+        }
+        Ok(index as i64 + 1)
+    }
+}
+
+// RFC 3339 style: "2024-06-01", "2024-06-01T12:30:00", with optional fraction and offset
+fn parse_iso(text: &str) -> Result<DateTime, String> {
+    let mut chars: Vec<char> = text.trim().chars().collect();
+    chars.push('\0');
+    let mut reader = Reader {
+        chars: &chars,
+        current: 0,
+    };
+    let mut parts = DateTime {
+        year: reader.number(4, true)?,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        nanosecond: 0,
+        offset: 0,
+    };
+    reader.literal('-')?;
+    parts.month = reader.number(2, true)?;
+    reader.literal('-')?;
+    parts.day = reader.number(2, true)?;
+    if matches!(reader.peek(), 'T' | 't' | ' ') {
+        reader.current += 1;
+        parts.hour = reader.number(2, true)?;
+        reader.literal(':')?;
+        parts.minute = reader.number(2, true)?;
+        if reader.peek() == ':' {
+            reader.current += 1;
+            parts.second = reader.number(2, true)?;
+            if reader.peek() == '.' {
+                reader.current += 1;
+                parts.nanosecond = reader.fraction()?;
+            }
+        }
+        if reader.peek() != '\0' {
+            parts.offset = reader.offset()?;
+        }
+    }
+    if reader.peek() != '\0' || reader.current != chars.len() - 1 {
+        return Err(format!("unexpected text at offset {}", reader.current));
+    }
+    parts.validate()?;
+    Ok(parts)
+}
+
+// strptime-style; supports %Y %y %m %d %H %M %S %f %b %B %z %j and %%
+fn parse(text: &str, pattern: &str) -> Result<DateTime, String> {
+    let mut chars: Vec<char> = text.chars().collect();
+    chars.push('\0');
+    let mut reader = Reader {
+        chars: &chars,
+        current: 0,
+    };
+    let mut parts = DateTime {
+        year: 1970,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        nanosecond: 0,
+        offset: 0,
+    };
+    let mut yearday = None;
+    let mut directives = pattern.chars();
+    while let Some(c) = directives.next() {
+        if c != '%' {
+            reader.literal(c)?;
+            continue;
+        }
+        match directives.next() {
+            Some('Y') => parts.year = reader.number(4, true)?,
+            Some('y') => parts.year = 2000 + reader.number(2, true)?,
+            Some('m') => parts.month = reader.number(2, false)?,
+            Some('d') => parts.day = reader.number(2, false)?,
+            Some('H') => parts.hour = reader.number(2, false)?,
+            Some('M') => parts.minute = reader.number(2, false)?,
+            Some('S') => parts.second = reader.number(2, false)?,
+            Some('f') => parts.nanosecond = reader.fraction()?,
+            Some('b' | 'B') => parts.month = reader.month_name()?,
+            Some('z') => parts.offset = reader.offset()?,
+            Some('j') => yearday = Some(reader.number(3, false)?),
+            Some('%') => reader.literal('%')?,
+            Some(other) => return Err(format!("unknown directive '%{}'", other)),
+            None => return Err("pattern ends with a lone '%'".to_string()),
+        }
+    }
+    if reader.current != chars.len() - 1 {
+        return Err(format!("unexpected text at offset {}", reader.current));
+    }
+    if let Some(yearday) = yearday {
+        let length = if is_leap(parts.year) { 366 } else { 365 };
+        if !(1..=length).contains(&yearday) {
+            return Err(format!("day of year {} is out of range", yearday));
+        }
+        let (_, month, day) = civil_from_days(days_from_civil(parts.year, 1, 1) + yearday - 1);
+        parts.month = month;
+        parts.day = day;
+    }
+    parts.validate()?;
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil};
+    use crate::{error::ReefError, testing};
+
+    #[test]
+    fn civil_dates_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        for days in (-800_000..800_000).step_by(997) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn formats_and_parses_timestamps() {
+        let interpreter = testing::run(
+            "
+            var ts = time.parse(\"2024-02-29T13:05:09.250+02:00\");
+            var utc = time.format(ts, \"%Y-%m-%d %H:%M:%S.%L %Z (%a %j)\");
+            var local = time.format(ts, \"%d %b %Y %I:%M %p %:z\", \"+05:30\");
+            var later = time.format(ts + time.days(1) + time.hours(1), \"%F %T\");
+            var custom = time.parse(\"03/Mar/2021 07:08\", \"%d/%b/%Y %H:%M\");
+            var built = time.date(2021, 3, 3, 7, 8, 0);
+            var parts = time.parts(time.date(2024, 12, 31), -60);
+            var elapsed = time.elapsed(time.instant()) >= 0;
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("ts"), "1709204709.25");
+        assert_eq!(render("utc"), "2024-02-29 11:05:09.250 UTC (Thu 060)");
+        assert_eq!(render("local"), "29 Feb 2024 04:35 PM +05:30");
+        assert_eq!(render("later"), "2024-03-01 12:05:09");
+        assert_eq!(render("custom"), render("built"));
+        assert_eq!(
+            render("parts"),
+            r#"{"year": 2024, "month": 12, "day": 30, "hour": 23, "minute": 0, "second": 0, "nanosecond": 0, "weekday": 1, "yearday": 365, "offset": -60}"#
+        );
+        assert_eq!(render("elapsed"), "true");
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for source in [
+            "time.parse(\"2023-02-29\");",
+            "time.parse(\"2023-01-01T10:00junk\");",
+            "time.parse(\"12:00\", \"%H:%M:%S\");",
+            "time.date(2024, 13, 1);",
+            "time.format(0, \"%Q\");",
+            "time.format(0, \"%Y\", \"+25:00\");",
+            "time.sleep(-1);",
+        ] {
+            assert!(testing::run(source).is_err(), "{} should fail", source);
+        }
+        for source in [
+            "time.format(0, \"%Y\", -9223372036854775808);",
+            "time.date(-9223372036854775808, 1, 1);",
+            "time.sleep(1e300);",
+        ] {
+            assert!(
+                matches!(testing::run(source), Err(ReefError::RuntimeError(_))),
+                "{} should be a runtime error",
+                source
+            );
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use crate::{Value, environment::EnvRef, func::NativeFunction, stdlib::type_error};
+
+pub fn define_natives(globals: &EnvRef) {
+    let natives = [
+        // tuple(list) freezes a list's current items, e.g. to use them as a map key
+        NativeFunction::new("tuple", 1, |_interpreter, args| match &args[0] {
+            Value::List(items) => Ok(Value::Tuple(Rc::new(items.borrow().clone()))),
+            Value::Tuple(items) => Ok(Value::Tuple(Rc::clone(items))),
+            other => Err(type_error("tuple", 0, "a list", other)),
+        }),
+    ];
+    for native in natives {
+        globals
+            .borrow_mut()
+            .define(native.name.clone(), Value::Callable(Rc::new(native)))
+            .expect("expect tuple function to be definable");
+    }
+}
+
+// methods are bound to the receiving tuple, which arrives as `args[0]`
+pub fn method(name: &str) -> Option<NativeFunction> {
+    let method = match name {
+        "len" => NativeFunction::new("len", 0, |_interpreter, args| {
+            Ok(Value::Int(receiver(&args).len() as i64))
+        }),
+        "toList" => NativeFunction::new("toList", 0, |_interpreter, args| {
+            Ok(Value::new_list(receiver(&args).to_vec()))
+        }),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn receiver(args: &[Value]) -> &[Value] {
+    match &args[0] {
+        Value::Tuple(items) => items,
+        _ => unreachable!("tuple methods are only bound to tuples"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+
+    #[test]
+    fn tuples_are_keys_and_return_values() {
+        let interpreter = testing::run(
+            "
+            fun divmod(a, b) { return (a / b, a - b * (a / b)); }
+            var result = divmod(7, 2);
+            var parts = [result[0], result[-1], result.len(), 3 in result];
+            var grid = {(0, 0): \"origin\"};
+            grid[(1, 2)] = \"point\";
+            grid[(rational(1, 2), rational(4, 2))] = \"half\";
+            var found = [grid[(0, 0)], grid[tuple([1, 2])], grid[(0.5, 2.0)]];
+            var shapes = [(), (1,), result.toList()];
+        ",
+        )
+        .expect("source should run");
+        let globals = interpreter.globals.borrow();
+        let render = |name: &str| interpreter.stringify(&globals.get(name).expect("defined"));
+        assert_eq!(render("result"), "(3, 1)");
+        assert_eq!(render("parts"), "[3, 1, 2, true]");
+        assert_eq!(
+            render("grid"),
+            r#"{(0, 0): "origin", (1, 2): "point", (1/2, 2): "half"}"#
+        );
+        assert_eq!(render("found"), r#"["origin", "point", "half"]"#);
+        assert_eq!(render("shapes"), "[(), (1,), [3, 1]]");
+        assert!(testing::run("var t = (1, 2); t[0] = 3;").is_err());
+        assert!(testing::run("var m = {([1], 2): 3};").is_err());
+    }
+}
@@ -35,9 +35,12 @@ pub enum StmtKind {
         name: Token,
         parameters: Vec<Token>,
         body: Vec<StmtKind>,
+        decorators: Vec<Expr>,
     },
     Class {
         name: Token,
         methods: Vec<StmtKind>,
+        fields: Vec<(Token, Expr)>,
+        sealed: bool,
     },
 }
@@ -0,0 +1,51 @@
+use std::{
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    Parser, Scanner, Value, error::ReefError, interpreter::Interpreter, resolver::Resolver,
+};
+
+pub fn run(source: &str) -> Result<Interpreter, ReefError> {
+    let mut scanner = Scanner::new(source.to_string());
+    let tokens = scanner.scan_tokens();
+    if let Some(e) = scanner.errors.first() {
+        return Err(e.clone());
+    }
+    let stmts = Parser::new(tokens).parse()?;
+    let mut resolver = Resolver::new(Interpreter::default());
+    resolver.resolve(&stmts)?;
+    let mut interpreter = resolver.interpreter;
+    interpreter.interpret(&stmts)?;
+    Ok(interpreter)
+}
+
+// runs `source` and returns the global `name` it leaves behind
+pub fn eval(source: &str, name: &str) -> Value {
+    let interpreter = run(source).expect("source should run");
+    interpreter
+        .globals
+        .borrow()
+        .get(name)
+        .expect("global should be defined")
+}
+
+pub fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("a free port")
+        .port()
+}
+
+// retries while the interpreter side is still getting ready to listen
+pub fn connect(port: u16) -> TcpStream {
+    for _ in 0..200 {
+        if let Ok(stream) = TcpStream::connect(("127.0.0.1", port)) {
+            return stream;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("nothing listening on port {}", port);
+}
@@ -1,16 +1,26 @@
+use bytes::Bytes;
+
+use crate::bigint::BigInt;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenType {
     LeftParen,
     RightParen,
     LeftBrace,
+    HashBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
+    Colon,
     Dot,
     Minus,
     Plus,
     Semicolon,
     Slash,
     Star,
+    Pipe,
+    At,
 
     // one or two character tokens.
     Bang,
@@ -24,7 +34,9 @@ pub enum TokenType {
 
     // literals.
     Identifier,
+    PrivateName,
     String,
+    Bytes,
     Number,
 
     // keywords.
@@ -35,6 +47,7 @@ pub enum TokenType {
     Fun,
     For,
     If,
+    In,
     Nil,
     Or,
     Print,
@@ -48,9 +61,12 @@ pub enum TokenType {
     Eof,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
     String(String),
+    Bytes(Bytes),
+    Int(i64),
+    BigInt(BigInt),
     Number(f64),
     Boolean(bool),
     Nil,